@@ -0,0 +1,35 @@
+//! Compares `multiply` with `multiply_simd` on `Matrix4<f32>`.
+//!
+//! Run with `cargo bench --features simd`.
+
+use std::hint::black_box;
+use std::time::Instant;
+
+use mafs::{multiply, multiply_simd, Mat4f, Tuple, Vec3};
+
+const ITERATIONS: u32 = 10_000_000;
+
+fn bench(name: &str, f: impl Fn(&Mat4f, &Mat4f) -> Mat4f) {
+    // Chaining through `acc` keeps the product from being hoisted out of the
+    // loop; a rotation keeps the accumulated values bounded.
+    let rotation = Mat4f::rotation_axis(&Vec3::new(1.0, 2.0, 3.0), 0.5);
+    let mut acc = Mat4f::identity();
+
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        acc = f(black_box(&acc), black_box(&rotation));
+    }
+    let elapsed = start.elapsed();
+    black_box(acc);
+
+    println!(
+        "{:>14}: {:>8.2} ns/iter",
+        name,
+        elapsed.as_nanos() as f64 / ITERATIONS as f64
+    );
+}
+
+fn main() {
+    bench("multiply", multiply);
+    bench("multiply_simd", multiply_simd);
+}
@@ -0,0 +1,175 @@
+use core::ops::{Add, Div, Sub};
+
+use crate::{One, Point3, Ray, Vec3};
+
+/// Axis-aligned bounding box spanning `min` to `max` inclusive.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Aabb<T> {
+    pub min: Point3<T>,
+    pub max: Point3<T>,
+}
+
+impl<T> Aabb<T> {
+    pub fn new(min: Point3<T>, max: Point3<T>) -> Self {
+        Self { min, max }
+    }
+
+    /// Smallest box containing every point, or `None` for an empty slice.
+    pub fn from_points(points: &[Point3<T>]) -> Option<Self>
+    where
+        T: Copy + PartialOrd,
+    {
+        let (first, rest) = points.split_first()?;
+        Some(rest.iter().fold(Self::new(*first, *first), |acc, p| Self {
+            min: acc.min.min_components(p),
+            max: acc.max.max_components(p),
+        }))
+    }
+
+    pub fn contains(&self, p: &Point3<T>) -> bool
+    where
+        T: PartialOrd,
+    {
+        p.x >= self.min.x
+            && p.y >= self.min.y
+            && p.z >= self.min.z
+            && p.x <= self.max.x
+            && p.y <= self.max.y
+            && p.z <= self.max.z
+    }
+
+    pub fn union(&self, other: &Self) -> Self
+    where
+        T: Copy + PartialOrd,
+    {
+        Self {
+            min: self.min.min_components(&other.min),
+            max: self.max.max_components(&other.max),
+        }
+    }
+
+    pub fn center(&self) -> Point3<T>
+    where
+        T: Copy + One + Add<Output = T> + Div<Output = T>,
+    {
+        let two = T::one() + T::one();
+        Point3 {
+            x: (self.min.x + self.max.x) / two,
+            y: (self.min.y + self.max.y) / two,
+            z: (self.min.z + self.max.z) / two,
+        }
+    }
+
+    /// Full size of the box along each axis.
+    pub fn extents(&self) -> Vec3<T>
+    where
+        T: Copy + Sub<Output = T>,
+    {
+        self.max - self.min
+    }
+
+    /// Slab test against `ray`. Returns the range of ray parameters `t >= 0`
+    /// spent inside the box, so a ray starting inside yields `(0, t_exit)`.
+    /// Axis-parallel rays are handled without dividing by zero.
+    pub fn intersect_ray(&self, ray: &Ray<T>) -> Option<(T, T)>
+    where
+        T: Copy + Default + Sub<Output = T> + Div<Output = T> + PartialOrd,
+    {
+        let zero = T::default();
+        let mut t_near = zero;
+        let mut t_far: Option<T> = None;
+
+        for axis in 0..3 {
+            let (o, d) = (ray.origin[axis], ray.direction[axis]);
+            let (lo, hi) = (self.min[axis], self.max[axis]);
+
+            if d == zero {
+                if o < lo || o > hi {
+                    return None;
+                }
+                continue;
+            }
+
+            let (mut t0, mut t1) = ((lo - o) / d, (hi - o) / d);
+            if t0 > t1 {
+                core::mem::swap(&mut t0, &mut t1);
+            }
+            if t0 > t_near {
+                t_near = t0;
+            }
+            let far = match t_far {
+                Some(t) if t < t1 => t,
+                _ => t1,
+            };
+            if t_near > far {
+                return None;
+            }
+            t_far = Some(far);
+        }
+
+        Some((t_near, t_far.unwrap_or(t_near)))
+    }
+}
+
+pub type Aabbd = Aabb<f64>;
+pub type Aabbf = Aabb<f32>;
+
+#[cfg(test)]
+mod tests {
+    use super::{Aabb, Aabbd};
+    use crate::{Point3, Rayd, Tuple, Vec3};
+
+    #[test]
+    fn build_from_points() {
+        let points = [
+            Point3::new(1.0, -2.0, 3.0),
+            Point3::new(-4.0, 5.0, 0.5),
+            Point3::new(2.0, 0.0, -1.0),
+        ];
+        let aabb = Aabbd::from_points(&points).unwrap();
+        assert_eq!(aabb.min, Point3::new(-4.0, -2.0, -1.0));
+        assert_eq!(aabb.max, Point3::new(2.0, 5.0, 3.0));
+        assert_eq!(aabb.center(), Point3::new(-1.0, 1.5, 1.0));
+        assert_eq!(aabb.extents(), Vec3::new(6.0, 7.0, 4.0));
+
+        for p in &points {
+            assert!(aabb.contains(p));
+        }
+        assert!(aabb.contains(&Point3::new(0.0, 0.0, 0.0)));
+        assert!(!aabb.contains(&Point3::new(3.0, 0.0, 0.0)));
+        assert!(!aabb.contains(&Point3::new(0.0, -2.5, 0.0)));
+
+        assert!(Aabbd::from_points(&[]).is_none());
+    }
+
+    #[test]
+    fn union_of_boxes() {
+        let a = Aabb::new(Point3::new(0, 0, 0), Point3::new(1, 1, 1));
+        let b = Aabb::new(Point3::new(-1, 2, 0), Point3::new(0, 3, 5));
+        let union = a.union(&b);
+        assert_eq!(union.min, Point3::new(-1, 0, 0));
+        assert_eq!(union.max, Point3::new(1, 3, 5));
+    }
+
+    #[test]
+    fn ray_intersection() {
+        let aabb = Aabbd::new(Point3::new(-1.0, -1.0, -1.0), Point3::new(1.0, 1.0, 1.0));
+
+        let hit = Rayd::new(Point3::new(-5.0, 0.5, 0.0), Vec3::new(1.0, 0.0, 0.0));
+        assert_eq!(aabb.intersect_ray(&hit), Some((4.0, 6.0)));
+
+        let miss = Rayd::new(Point3::new(-5.0, 2.0, 0.0), Vec3::new(1.0, 0.0, 0.0));
+        assert_eq!(aabb.intersect_ray(&miss), None);
+
+        let away = Rayd::new(Point3::new(-5.0, 0.0, 0.0), Vec3::new(-1.0, 0.0, 0.0));
+        assert_eq!(aabb.intersect_ray(&away), None);
+
+        let inside = Rayd::new(Point3::new(0.0, 0.0, 0.0), Vec3::new(0.0, 0.0, 1.0));
+        assert_eq!(aabb.intersect_ray(&inside), Some((0.0, 1.0)));
+
+        let diagonal = Rayd::new(Point3::new(-2.0, -2.0, 0.0), Vec3::new(1.0, 1.0, 0.0));
+        let (t_near, t_far) = aabb.intersect_ray(&diagonal).unwrap();
+        assert!((t_near - 2.0f64.sqrt()).abs() < 1e-12);
+        assert!((t_far - 3.0 * 2.0f64.sqrt()).abs() < 1e-12);
+    }
+}
@@ -1,9 +1,98 @@
-use std::ops::{Add, Div, Index, IndexMut, Mul, MulAssign, Sub};
+use core::fmt;
+use core::iter::{Product, Sum};
+use core::ops::{
+    Add, AddAssign, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Neg, Sub, SubAssign,
+};
 
 pub trait Sqrt {
     fn sqrt(&self) -> Self;
 }
 
+pub trait Abs {
+    fn abs(&self) -> Self;
+}
+
+pub trait Trig {
+    fn sin(&self) -> Self;
+    fn cos(&self) -> Self;
+    fn tan(&self) -> Self;
+}
+
+pub trait Acos {
+    fn acos(&self) -> Self;
+}
+
+pub trait Atan2 {
+    /// Four-quadrant arctangent of `self / x`, in `[-pi, pi]`.
+    fn atan2(&self, x: &Self) -> Self;
+}
+
+pub trait Round {
+    fn floor(&self) -> Self;
+    fn ceil(&self) -> Self;
+    /// Rounds half-way cases away from zero.
+    fn round(&self) -> Self;
+    /// Fractional part with the sign of `self`, i.e. `self - trunc(self)`.
+    fn fract(&self) -> Self;
+}
+
+/// Floating-point classification.
+pub trait Finite {
+    fn is_finite(&self) -> bool;
+    fn is_nan(&self) -> bool;
+}
+
+/// Exact bitwise equality. Unlike `==` on floats, `0.0` and `-0.0` differ
+/// and a NaN equals itself when the payloads match.
+pub trait BitEq {
+    fn bit_eq(&self, other: &Self) -> bool;
+}
+
+pub trait Zero {
+    fn zero() -> Self;
+}
+
+pub trait One {
+    fn one() -> Self;
+}
+
+/// Archimedes' constant, implemented for the float types.
+pub trait Pi {
+    fn pi() -> Self;
+}
+
+/// Conversion of literal constants such as tolerances into the scalar type,
+/// implemented for the float types.
+pub trait FromF64 {
+    fn from_f64(value: f64) -> Self;
+}
+
+/// Machine epsilon, the gap between 1 and the next representable value.
+/// Scale it by the magnitude of the operands to get a rounding tolerance.
+/// Only implemented for the float types, so algorithms that need division
+/// to be exact up to rounding, like `Matrix4::inversed`, reject integers.
+pub trait Epsilon {
+    fn epsilon() -> Self;
+}
+
+/// Overflow-checked arithmetic, implemented for the primitive integer types.
+pub trait CheckedArith: Sized {
+    fn checked_add(&self, rhs: &Self) -> Option<Self>;
+    fn checked_mul(&self, rhs: &Self) -> Option<Self>;
+}
+
+/// Tolerant comparison. Aggregate types compare equal only when every
+/// component is within `epsilon` of its counterpart.
+pub trait ApproxEq<T> {
+    fn approx_eq(&self, other: &Self, epsilon: T) -> bool;
+}
+
+/// Interpolation between two values, with `t = 0` giving `self` and
+/// `t = 1` giving `other`.
+pub trait Mix<T> {
+    fn mix(&self, other: &Self, t: T) -> Self;
+}
+
 pub trait Tuple<T> {
     fn new(x: T, y: T, z: T) -> Self
     where
@@ -20,40 +109,89 @@ pub trait Tuple<T> {
             + Mul<Output = T>
             + Div<Output = T>
             + MulAssign
+            + Zero
+            + One
             + Sqrt
-            + PartialOrd,
-        f32: Into<T>,
-        f64: Into<T>;
+            + PartialOrd;
 }
 
-pub trait Vector<T>: Tuple<T> {
+/// Operations shared by `Vec2`, `Vec3` and `Vec4`, so generic code can
+/// accept any of them. Implementors provide `dot` and `normalize`. The
+/// dimension-specific parts live in `Vector2` and `Vector3`.
+pub trait Vector<T> {
     fn dot(lhs: &Self, rhs: &Self) -> T
     where
         T: Copy + Add<Output = T> + Mul<Output = T>;
 
-    fn cross(&self, other: &Self) -> Self
+    fn magnitude(&self) -> T
     where
-        T: Copy + Add<Output = T> + Mul<Output = T> + Sub<Output = T>;
+        T: Copy + Add<Output = T> + Mul<Output = T> + Sqrt,
+    {
+        self.magnitude_squared().sqrt()
+    }
 
-    fn magnitude(&self) -> T
+    fn magnitude_squared(&self) -> T
     where
-        T: Copy + Add<Output = T> + Mul<Output = T> + Sqrt;
+        T: Copy + Add<Output = T> + Mul<Output = T>,
+    {
+        Self::dot(self, self)
+    }
+
+    /// Scales to unit length. A zero vector is left unchanged.
+    fn normalize(&mut self)
+    where
+        T: Copy
+            + Add<Output = T>
+            + Mul<Output = T>
+            + Div<Output = T>
+            + MulAssign
+            + Zero
+            + One
+            + Sqrt
+            + PartialOrd;
+}
+
+/// The 3D-only part of the vector interface; the rest lives in `Vector`.
+pub trait Vector3<T>: Vector<T> {
+    fn cross(&self, other: &Self) -> Self
+    where
+        T: Copy + Add<Output = T> + Mul<Output = T> + Sub<Output = T>;
 }
 
 pub trait Point<T>: Tuple<T> {
     fn distance_from_origin(&self) -> T
     where
         T: Copy + Add<Output = T> + Mul<Output = T> + Sqrt;
+
+    fn distance(&self, other: &Self) -> T
+    where
+        T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Sqrt;
+
+    fn distance_squared(&self, other: &Self) -> T
+    where
+        T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T>;
 }
 
-#[derive(Default, Clone, Copy, PartialEq)]
+/// With the `serde` feature, serialized as an `{x, y, z}` object.
+///
+/// The ordering is lexicographic (x, then y, then z), not by magnitude. The
+/// component-wise versions are named `min_components` and so on, so they
+/// never collide with `Ord::min`, `Ord::max` or `Ord::clamp`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Vec3<T> {
     pub x: T,
     pub y: T,
     pub z: T,
 }
 
-#[derive(Default, Clone, Copy, PartialEq)]
+/// With the `serde` feature, serialized as an `{x, y, z}` object.
+///
+/// The ordering is lexicographic (x, then y, then z), not by magnitude. The
+/// component-wise versions are named `min_components` and so on, so they
+/// never collide with `Ord::min`, `Ord::max` or `Ord::clamp`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Point3<T> {
     pub x: T,
     pub y: T,
@@ -84,18 +222,12 @@ impl<T> Tuple<T> for Vec3<T> {
             + Mul<Output = T>
             + Div<Output = T>
             + MulAssign
+            + Zero
+            + One
             + Sqrt
             + PartialOrd,
-        f32: Into<T>,
-        f64: Into<T>,
     {
-        let len = self.magnitude();
-        if len > 0.0.into() {
-            let inv_len = 1.0.into() / len;
-            self.x *= inv_len;
-            self.y *= inv_len;
-            self.z *= inv_len;
-        }
+        Self::normalize(self)
     }
 }
 
@@ -123,323 +255,2795 @@ impl<T> Tuple<T> for Point3<T> {
             + Mul<Output = T>
             + Div<Output = T>
             + MulAssign
+            + Zero
+            + One
             + Sqrt
             + PartialOrd,
-        f32: Into<T>,
-        f64: Into<T>,
     {
-        let len = self.distance_from_origin();
-        if len > 0.0.into() {
-            let inv_len = 1.0.into() / len;
-            self.x *= inv_len;
-            self.y *= inv_len;
-            self.z *= inv_len;
-        }
+        Self::normalize(self)
     }
 }
 
-impl Sqrt for f32 {
-    fn sqrt(&self) -> Self {
-        f32::sqrt(*self)
+impl<T> Vec3<T> {
+    pub fn from_point(p: &Point3<T>) -> Self
+    where
+        T: Copy,
+    {
+        Self {
+            x: p.x,
+            y: p.y,
+            z: p.z,
+        }
     }
-}
 
-impl Sqrt for f64 {
-    fn sqrt(&self) -> Self {
-        f64::sqrt(*self)
+    /// Vector with every component set to `value`.
+    pub fn splat(value: T) -> Self
+    where
+        T: Copy,
+    {
+        Self {
+            x: value,
+            y: value,
+            z: value,
+        }
     }
-}
 
-impl<T> Vector<T> for Vec3<T> {
-    fn dot(lhs: &Self, rhs: &Self) -> T
+    pub fn unit_x() -> Self
     where
-        T: Copy + Add<Output = T> + Mul<Output = T>,
+        T: Zero + One,
     {
-        lhs.x * rhs.x + lhs.y * rhs.y + lhs.z * rhs.z
+        Self {
+            x: T::one(),
+            y: T::zero(),
+            z: T::zero(),
+        }
     }
 
-    fn cross(&self, other: &Self) -> Self
+    pub fn unit_y() -> Self
     where
-        T: Copy + Add<Output = T> + Mul<Output = T> + Sub<Output = T>,
+        T: Zero + One,
     {
-        Self::new(
-            self.y * other.z - self.z * other.y,
-            self.z * other.x - self.x * other.z,
-            self.x * other.y - self.y * other.x,
-        )
+        Self {
+            x: T::zero(),
+            y: T::one(),
+            z: T::zero(),
+        }
     }
 
-    fn magnitude(&self) -> T
+    pub fn unit_z() -> Self
     where
-        T: Copy + Add<Output = T> + Mul<Output = T> + Sqrt,
+        T: Zero + One,
     {
-        let value = self.x * self.x + self.y * self.y + self.z * self.z;
-        value.sqrt()
+        Self {
+            x: T::zero(),
+            y: T::zero(),
+            z: T::one(),
+        }
     }
-}
-
-impl<T: Add<Output = T>> Add for Vec3<T> {
-    type Output = Self;
 
-    fn add(self, rhs: Self) -> Self::Output {
+    pub fn zero() -> Self
+    where
+        T: Zero,
+    {
         Self {
-            x: self.x + rhs.x,
-            y: self.y + rhs.y,
-            z: self.z + rhs.z,
+            x: T::zero(),
+            y: T::zero(),
+            z: T::zero(),
         }
     }
-}
 
-impl<T: Sub<Output = T>> Sub for Vec3<T> {
-    type Output = Self;
-
-    fn sub(self, rhs: Self) -> Self::Output {
+    pub fn one() -> Self
+    where
+        T: One,
+    {
         Self {
-            x: self.x - rhs.x,
-            y: self.y - rhs.y,
-            z: self.z - rhs.z,
+            x: T::one(),
+            y: T::one(),
+            z: T::one(),
         }
     }
-}
-
-impl<T: Copy + Add<Output = T> + Mul<Output = T> + Sub<Output = T>> Mul for Vec3<T> {
-    type Output = Self;
 
-    fn mul(self, rhs: Self) -> Self::Output {
-        self.cross(&rhs)
+    /// Scales to unit length. A zero vector is left unchanged. Inherent so
+    /// that `v.normalize()` stays unambiguous with both `Tuple` and `Vector`
+    /// in scope.
+    pub fn normalize(&mut self)
+    where
+        T: Copy
+            + Add<Output = T>
+            + Mul<Output = T>
+            + Div<Output = T>
+            + MulAssign
+            + Zero
+            + One
+            + Sqrt
+            + PartialOrd,
+    {
+        let len = self.magnitude();
+        if len > T::zero() {
+            let inv_len = T::one() / len;
+            self.x *= inv_len;
+            self.y *= inv_len;
+            self.z *= inv_len;
+        }
     }
-}
 
-impl<T: Add<Output = T>> Add for Point3<T> {
-    type Output = Self;
+    /// Normalized copy of `self`. A zero vector is returned unchanged.
+    pub fn normalized(&self) -> Self
+    where
+        T: Copy
+            + Add<Output = T>
+            + Mul<Output = T>
+            + Div<Output = T>
+            + MulAssign
+            + Zero
+            + One
+            + Sqrt
+            + PartialOrd,
+    {
+        let mut result = *self;
+        result.normalize();
+        result
+    }
 
-    fn add(self, rhs: Self) -> Self::Output {
-        Self {
-            x: self.x + rhs.x,
-            y: self.y + rhs.y,
-            z: self.z + rhs.z,
+    /// Normalizes in place and returns `true`, or leaves `self` untouched and
+    /// returns `false` when the magnitude is too small to divide by.
+    pub fn try_normalize(&mut self) -> bool
+    where
+        T: Copy
+            + One
+            + Add<Output = T>
+            + Mul<Output = T>
+            + Div<Output = T>
+            + Sqrt
+            + FromF64
+            + PartialOrd,
+    {
+        let len = self.magnitude();
+        if len <= T::from_f64(1.0e-12) {
+            return false;
         }
+        let inv_len = T::one() / len;
+        *self = *self * inv_len;
+        true
     }
-}
 
-impl<T: Sub<Output = T>> Sub for Point3<T> {
-    type Output = Self;
+    /// Normalized copy of `self`, or `fallback` when the magnitude is too
+    /// small to divide by.
+    pub fn normalized_or(&self, fallback: Self) -> Self
+    where
+        T: Copy
+            + One
+            + Add<Output = T>
+            + Mul<Output = T>
+            + Div<Output = T>
+            + Sqrt
+            + FromF64
+            + PartialOrd,
+    {
+        let mut result = *self;
+        if result.try_normalize() {
+            result
+        } else {
+            fallback
+        }
+    }
 
-    fn sub(self, rhs: Self) -> Self::Output {
-        Self {
-            x: self.x - rhs.x,
-            y: self.y - rhs.y,
-            z: self.z - rhs.z,
+    /// Rescales `self` to length `max` if it is longer, otherwise returns it
+    /// unchanged. `max` must not be negative.
+    pub fn clamp_magnitude(&self, max: T) -> Self
+    where
+        T: Copy + Add<Output = T> + Mul<Output = T> + Div<Output = T> + Sqrt + PartialOrd,
+    {
+        let len = self.magnitude();
+        if len > max {
+            *self * (max / len)
+        } else {
+            *self
         }
     }
-}
 
-impl<T> Point<T> for Point3<T> {
-    fn distance_from_origin(&self) -> T
+    /// Vector pointing the same way as `self` with length `len`. A zero
+    /// vector is returned unchanged.
+    pub fn with_magnitude(&self, len: T) -> Self
     where
-        T: Copy + Add<Output = T> + Mul<Output = T> + Sqrt,
+        T: Copy + Zero + Add<Output = T> + Mul<Output = T> + Div<Output = T> + Sqrt + PartialOrd,
     {
-        let value = self.x * self.x + self.y * self.y + self.z * self.z;
-        value.sqrt()
+        let current = self.magnitude();
+        if current > T::zero() {
+            *self * (len / current)
+        } else {
+            *self
+        }
     }
-}
 
-impl<T> Index<usize> for Vec3<T> {
-    type Output = T;
+    /// Linear interpolation `a + (b - a) * t`. `t` is not clamped.
+    pub fn lerp(a: &Self, b: &Self, t: T) -> Self
+    where
+        T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+    {
+        *a + (*b - *a) * t
+    }
 
-    fn index(&self, index: usize) -> &Self::Output {
-        match index {
-            0 => &self.x,
-            1 => &self.y,
-            2 => &self.z,
-            _ => panic!("Out of bound access in Vec3<T>!"),
-        }
+    /// Cubic Bezier curve with control vectors `p0` to `p3`, evaluated like
+    /// `Point3::bezier`.
+    pub fn bezier(p0: &Self, p1: &Self, p2: &Self, p3: &Self, t: T) -> Self
+    where
+        T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+    {
+        let (a, b, c) = (
+            Self::lerp(p0, p1, t),
+            Self::lerp(p1, p2, t),
+            Self::lerp(p2, p3, t),
+        );
+        let (d, e) = (Self::lerp(&a, &b, t), Self::lerp(&b, &c, t));
+        Self::lerp(&d, &e, t)
     }
-}
 
-impl<T> IndexMut<usize> for Vec3<T> {
-    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
-        match index {
-            0 => &mut self.x,
-            1 => &mut self.y,
-            2 => &mut self.z,
-            _ => panic!("Out of bound access in Vec3<T>!"),
-        }
+    /// Method form of `Vector::dot`, so `a.dot(&b)` and `Vec3::dot(&a, &b)`
+    /// both compile.
+    pub fn dot(&self, other: &Self) -> T
+    where
+        T: Copy + Add<Output = T> + Mul<Output = T>,
+    {
+        <Self as Vector<T>>::dot(self, other)
     }
-}
 
-impl<T> Index<usize> for Point3<T> {
-    type Output = T;
+    pub fn distance(&self, other: &Self) -> T
+    where
+        T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Sqrt,
+    {
+        (*self - *other).magnitude()
+    }
 
-    fn index(&self, index: usize) -> &Self::Output {
-        match index {
-            0 => &self.x,
-            1 => &self.y,
-            2 => &self.z,
-            _ => panic!("Out of bound access in Point3<T>!"),
+    /// Element-wise (Hadamard) product. `Vec3 * Vec3` is the cross product.
+    pub fn component_mul(&self, other: &Self) -> Self
+    where
+        T: Copy + Mul<Output = T>,
+    {
+        Self {
+            x: self.x * other.x,
+            y: self.y * other.y,
+            z: self.z * other.z,
         }
     }
-}
 
-impl<T> IndexMut<usize> for Point3<T> {
-    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
-        match index {
-            0 => &mut self.x,
-            1 => &mut self.y,
-            2 => &mut self.z,
-            _ => panic!("Out of bound access in Point3<T>!"),
+    pub fn component_div(&self, other: &Self) -> Self
+    where
+        T: Copy + Div<Output = T>,
+    {
+        Self {
+            x: self.x / other.x,
+            y: self.y / other.y,
+            z: self.z / other.z,
         }
     }
-}
 
-pub type Vec3d = Vec3<f64>;
-pub type Vec3f = Vec3<f32>;
+    pub fn distance_squared(&self, other: &Self) -> T
+    where
+        T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+    {
+        (*self - *other).magnitude_squared()
+    }
+
+    /// Reflects `incident` about `normal`, which must be normalized.
+    pub fn reflect(incident: &Self, normal: &Self) -> Self
+    where
+        T: Copy + One + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+    {
+        let two = T::one() + T::one();
+        *incident - *normal * (two * Vec3::dot(incident, normal))
+    }
+
+    /// Refracts `incident` through a surface with the given `normal` using
+    /// Snell's law, where `eta` is the ratio of the refractive indices
+    /// (outside over inside). Both vectors must be normalized. Returns `None`
+    /// on total internal reflection.
+    pub fn refract(incident: &Self, normal: &Self, eta: T) -> Option<Self>
+    where
+        T: Copy
+            + Default
+            + One
+            + Add<Output = T>
+            + Sub<Output = T>
+            + Mul<Output = T>
+            + Sqrt
+            + PartialOrd,
+    {
+        let cos_i = Vec3::dot(incident, normal);
+        let k = T::one() - eta * eta * (T::one() - cos_i * cos_i);
+        if k < T::default() {
+            return None;
+        }
+        Some(*incident * eta - *normal * (eta * cos_i + k.sqrt()))
+    }
+
+    /// Projection of `self` onto `onto`. Projecting onto the zero vector
+    /// yields the zero vector.
+    pub fn project_onto(&self, onto: &Self) -> Self
+    where
+        T: Copy + Default + Add<Output = T> + Mul<Output = T> + Div<Output = T> + PartialEq,
+    {
+        let len_squared = Vec3::dot(onto, onto);
+        if len_squared == T::default() {
+            return Self::zeros();
+        }
+        *onto * (Vec3::dot(self, onto) / len_squared)
+    }
+
+    /// Component of `self` perpendicular to `from`, i.e.
+    /// `self - self.project_onto(from)`.
+    pub fn reject_from(&self, from: &Self) -> Self
+    where
+        T: Copy
+            + Default
+            + Add<Output = T>
+            + Sub<Output = T>
+            + Mul<Output = T>
+            + Div<Output = T>
+            + PartialEq,
+    {
+        *self - self.project_onto(from)
+    }
+
+    /// Unsigned angle between `a` and `b` in radians. The cosine is clamped
+    /// to [-1, 1] so rounding errors can't produce NaN.
+    pub fn angle_between(a: &Self, b: &Self) -> T
+    where
+        T: Copy
+            + One
+            + Add<Output = T>
+            + Mul<Output = T>
+            + Div<Output = T>
+            + Neg<Output = T>
+            + Sqrt
+            + Acos
+            + PartialOrd,
+    {
+        let one = T::one();
+        let cos = Vec3::dot(a, b) / (a.magnitude() * b.magnitude());
+        let cos = if cos > one {
+            one
+        } else if cos < -one {
+            -one
+        } else {
+            cos
+        };
+        cos.acos()
+    }
+
+    /// Two unit vectors `(t, b)` such that `(t, b, self)` is a right-handed
+    /// orthonormal basis, i.e. `t x b == self`. `self` must be normalized.
+    /// Uses the branchless construction of Duff et al. (2017), which stays
+    /// accurate for every direction including `-z`.
+    pub fn orthonormal_basis(&self) -> (Self, Self)
+    where
+        T: Copy
+            + Zero
+            + One
+            + Add<Output = T>
+            + Mul<Output = T>
+            + Div<Output = T>
+            + Neg<Output = T>
+            + PartialOrd,
+    {
+        let n = self;
+        let sign = if n.z >= T::zero() {
+            T::one()
+        } else {
+            -T::one()
+        };
+        let a = -T::one() / (sign + n.z);
+        let b = n.x * n.y * a;
+        (
+            Self {
+                x: T::one() + sign * n.x * n.x * a,
+                y: sign * b,
+                z: -sign * n.x,
+            },
+            Self {
+                x: b,
+                y: sign + n.y * n.y * a,
+                z: -n.y,
+            },
+        )
+    }
+
+    pub fn cross(&self, other: &Self) -> Self
+    where
+        T: Copy + Mul<Output = T> + Sub<Output = T>,
+    {
+        Self {
+            x: self.y * other.z - self.z * other.y,
+            y: self.z * other.x - self.x * other.z,
+            z: self.x * other.y - self.y * other.x,
+        }
+    }
+
+    /// `a . (b x c)`, the signed volume of the parallelepiped spanned by the
+    /// three vectors. Equals the determinant of the matrix with rows `a`, `b`
+    /// and `c`.
+    pub fn scalar_triple(a: &Self, b: &Self, c: &Self) -> T
+    where
+        T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+    {
+        Vec3::dot(a, &b.cross(c))
+    }
+
+    /// `a x (b x c)`.
+    pub fn vector_triple(a: &Self, b: &Self, c: &Self) -> Self
+    where
+        T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+    {
+        a.cross(&b.cross(c))
+    }
+
+    /// Unnormalized normal `(b - a) x (c - a)` of the triangle `abc`. Points
+    /// towards the side from which the vertices appear counter-clockwise, and
+    /// its magnitude is twice the triangle's area.
+    pub fn triangle_normal(a: &Point3<T>, b: &Point3<T>, c: &Point3<T>) -> Self
+    where
+        T: Copy + Mul<Output = T> + Sub<Output = T>,
+    {
+        b.difference(a).cross(&c.difference(a))
+    }
+
+    /// Area of the triangle `abc`, i.e. half the magnitude of
+    /// `triangle_normal`.
+    pub fn triangle_area(a: &Point3<T>, b: &Point3<T>, c: &Point3<T>) -> T
+    where
+        T: Copy
+            + One
+            + Add<Output = T>
+            + Sub<Output = T>
+            + Mul<Output = T>
+            + Div<Output = T>
+            + Sqrt,
+    {
+        Self::triangle_normal(a, b, c).magnitude() / (T::one() + T::one())
+    }
+
+    /// Component-wise minimum. Follows `PartialOrd`: when a comparison
+    /// involving NaN fails, the component of `self` is kept.
+    pub fn min_components(&self, other: &Self) -> Self
+    where
+        T: Copy + PartialOrd,
+    {
+        Self {
+            x: if other.x < self.x { other.x } else { self.x },
+            y: if other.y < self.y { other.y } else { self.y },
+            z: if other.z < self.z { other.z } else { self.z },
+        }
+    }
+
+    /// Component-wise maximum. Follows `PartialOrd`: when a comparison
+    /// involving NaN fails, the component of `self` is kept.
+    pub fn max_components(&self, other: &Self) -> Self
+    where
+        T: Copy + PartialOrd,
+    {
+        Self {
+            x: if other.x > self.x { other.x } else { self.x },
+            y: if other.y > self.y { other.y } else { self.y },
+            z: if other.z > self.z { other.z } else { self.z },
+        }
+    }
+
+    /// Clamps every component into `[lo, hi]`, with the same NaN behavior as
+    /// `min_components` and `max_components`.
+    pub fn clamp_components(&self, lo: &Self, hi: &Self) -> Self
+    where
+        T: Copy + PartialOrd,
+    {
+        self.max_components(lo).min_components(hi)
+    }
+
+    pub fn abs(&self) -> Self
+    where
+        T: Abs,
+    {
+        Self {
+            x: self.x.abs(),
+            y: self.y.abs(),
+            z: self.z.abs(),
+        }
+    }
+
+    pub fn floor(&self) -> Self
+    where
+        T: Round,
+    {
+        Self {
+            x: self.x.floor(),
+            y: self.y.floor(),
+            z: self.z.floor(),
+        }
+    }
+
+    pub fn ceil(&self) -> Self
+    where
+        T: Round,
+    {
+        Self {
+            x: self.x.ceil(),
+            y: self.y.ceil(),
+            z: self.z.ceil(),
+        }
+    }
+
+    /// Rounds every component, with half-way cases away from zero.
+    pub fn round(&self) -> Self
+    where
+        T: Round,
+    {
+        Self {
+            x: self.x.round(),
+            y: self.y.round(),
+            z: self.z.round(),
+        }
+    }
+
+    /// Fractional part of every component, keeping the component's sign.
+    pub fn fract(&self) -> Self
+    where
+        T: Round,
+    {
+        Self {
+            x: self.x.fract(),
+            y: self.y.fract(),
+            z: self.z.fract(),
+        }
+    }
+
+    /// Compares the bit patterns of every component. Unlike `==`, this
+    /// tells `0.0` from `-0.0` and treats identical NaNs as equal, which is
+    /// what hashing a serialized form needs.
+    pub fn bit_eq(&self, other: &Self) -> bool
+    where
+        T: BitEq,
+    {
+        self.x.bit_eq(&other.x) && self.y.bit_eq(&other.y) && self.z.bit_eq(&other.z)
+    }
+
+    /// `true` when no component is infinite or NaN.
+    pub fn is_finite(&self) -> bool
+    where
+        T: Finite,
+    {
+        self.x.is_finite() && self.y.is_finite() && self.z.is_finite()
+    }
+
+    /// `true` when any component is NaN.
+    pub fn is_nan(&self) -> bool
+    where
+        T: Finite,
+    {
+        self.x.is_nan() || self.y.is_nan() || self.z.is_nan()
+    }
+
+    /// Applies `f` to every component, possibly changing the component type.
+    pub fn map<U>(&self, f: impl Fn(T) -> U) -> Vec3<U>
+    where
+        T: Copy,
+    {
+        Vec3 {
+            x: f(self.x),
+            y: f(self.y),
+            z: f(self.z),
+        }
+    }
+
+    /// Combines the two vectors component-wise with `f`.
+    pub fn zip_with(&self, other: &Self, f: impl Fn(T, T) -> T) -> Self
+    where
+        T: Copy,
+    {
+        Self {
+            x: f(self.x, other.x),
+            y: f(self.y, other.y),
+            z: f(self.z, other.z),
+        }
+    }
+
+    /// Iterates over the components in x, y, z order.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        [&self.x, &self.y, &self.z].into_iter()
+    }
+
+    pub fn get(&self, i: usize) -> Option<&T> {
+        match i {
+            0 => Some(&self.x),
+            1 => Some(&self.y),
+            2 => Some(&self.z),
+            _ => None,
+        }
+    }
+
+    pub fn get_mut(&mut self, i: usize) -> Option<&mut T> {
+        match i {
+            0 => Some(&mut self.x),
+            1 => Some(&mut self.y),
+            2 => Some(&mut self.z),
+            _ => None,
+        }
+    }
+}
+
+impl<T> Point3<T> {
+    /// Scales the position vector to unit length. The origin is left
+    /// unchanged.
+    pub fn normalize(&mut self)
+    where
+        T: Copy
+            + Add<Output = T>
+            + Mul<Output = T>
+            + Div<Output = T>
+            + MulAssign
+            + Zero
+            + One
+            + Sqrt
+            + PartialOrd,
+    {
+        let len = self.distance_from_origin();
+        if len > T::zero() {
+            let inv_len = T::one() / len;
+            self.x *= inv_len;
+            self.y *= inv_len;
+            self.z *= inv_len;
+        }
+    }
+
+    pub fn from_vec(v: &Vec3<T>) -> Self
+    where
+        T: Copy,
+    {
+        Self {
+            x: v.x,
+            y: v.y,
+            z: v.z,
+        }
+    }
+
+    /// Point with every coordinate set to `value`.
+    pub fn splat(value: T) -> Self
+    where
+        T: Copy,
+    {
+        Self {
+            x: value,
+            y: value,
+            z: value,
+        }
+    }
+
+    /// Normalized copy of `self`. The origin is returned unchanged.
+    pub fn normalized(&self) -> Self
+    where
+        T: Copy
+            + Add<Output = T>
+            + Mul<Output = T>
+            + Div<Output = T>
+            + MulAssign
+            + Zero
+            + One
+            + Sqrt
+            + PartialOrd,
+    {
+        let mut result = *self;
+        result.normalize();
+        result
+    }
+
+    /// Displacement vector pointing from `other` to `self`.
+    pub fn difference(&self, other: &Self) -> Vec3<T>
+    where
+        T: Copy + Sub<Output = T>,
+    {
+        *self - *other
+    }
+
+    /// Linear interpolation `a + (b - a) * t`. `t` is not clamped.
+    pub fn lerp(a: &Self, b: &Self, t: T) -> Self
+    where
+        T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+    {
+        Self {
+            x: a.x + (b.x - a.x) * t,
+            y: a.y + (b.y - a.y) * t,
+            z: a.z + (b.z - a.z) * t,
+        }
+    }
+
+    /// Weighted combination `a * u + b * v + c * w`. With `u + v + w = 1`
+    /// these are barycentric coordinates in the triangle `abc`.
+    pub fn barycentric(a: &Self, b: &Self, c: &Self, u: T, v: T, w: T) -> Self
+    where
+        T: Copy + Add<Output = T> + Mul<Output = T>,
+    {
+        Self {
+            x: a.x * u + b.x * v + c.x * w,
+            y: a.y * u + b.y * v + c.y * w,
+            z: a.z * u + b.z * v + c.z * w,
+        }
+    }
+
+    /// `barycentric` with the weight of `c` derived as `1 - u - v`.
+    pub fn barycentric_uv(a: &Self, b: &Self, c: &Self, u: T, v: T) -> Self
+    where
+        T: Copy + One + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+    {
+        Self::barycentric(a, b, c, u, v, T::one() - u - v)
+    }
+
+    /// Uniform Catmull-Rom segment from `p1` (`t = 0`) to `p2` (`t = 1`),
+    /// with `p0` and `p3` as the neighbouring control points. Evaluated as a
+    /// cubic Hermite curve with tangents `(p2 - p0) / 2` at `p1` and
+    /// `(p3 - p1) / 2` at `p2`, so consecutive segments of a point sequence
+    /// join with matching tangents.
+    pub fn catmull_rom(p0: &Self, p1: &Self, p2: &Self, p3: &Self, t: T) -> Self
+    where
+        T: Copy + One + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Div<Output = T>,
+    {
+        let one = T::one();
+        let two = one + one;
+        let (t2, t3) = (t * t, t * t * t);
+        let h01 = t2 * (two + one - two * t);
+        let h00 = one - h01;
+        let h10 = t3 - two * t2 + t;
+        let h11 = t3 - t2;
+        let eval = |a: T, b: T, c: T, d: T| {
+            h00 * b + h10 * ((c - a) / two) + h01 * c + h11 * ((d - b) / two)
+        };
+
+        Self {
+            x: eval(p0.x, p1.x, p2.x, p3.x),
+            y: eval(p0.y, p1.y, p2.y, p3.y),
+            z: eval(p0.z, p1.z, p2.z, p3.z),
+        }
+    }
+
+    /// Cubic Bezier curve from `p0` (`t = 0`) to `p3` (`t = 1`) with `p1`
+    /// and `p2` as the inner control points, evaluated by de Casteljau's
+    /// repeated `lerp`.
+    pub fn bezier(p0: &Self, p1: &Self, p2: &Self, p3: &Self, t: T) -> Self
+    where
+        T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+    {
+        let (a, b, c) = (
+            Self::lerp(p0, p1, t),
+            Self::lerp(p1, p2, t),
+            Self::lerp(p2, p3, t),
+        );
+        let (d, e) = (Self::lerp(&a, &b, t), Self::lerp(&b, &c, t));
+        Self::lerp(&d, &e, t)
+    }
+
+    /// Tangent of `bezier` at `t`, i.e. its derivative with respect to `t`.
+    /// Equals three times the span of the last de Casteljau step, so it is
+    /// `3 * (p1 - p0)` at `t = 0` and `3 * (p3 - p2)` at `t = 1`.
+    pub fn bezier_derivative(p0: &Self, p1: &Self, p2: &Self, p3: &Self, t: T) -> Vec3<T>
+    where
+        T: Copy + One + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+    {
+        let (a, b, c) = (
+            Self::lerp(p0, p1, t),
+            Self::lerp(p1, p2, t),
+            Self::lerp(p2, p3, t),
+        );
+        let (d, e) = (Self::lerp(&a, &b, t), Self::lerp(&b, &c, t));
+        e.difference(&d) * (T::one() + T::one() + T::one())
+    }
+
+    /// Component-wise minimum, with the same NaN behavior as
+    /// `Vec3::min_components`.
+    pub fn min_components(&self, other: &Self) -> Self
+    where
+        T: Copy + PartialOrd,
+    {
+        Self::from_vec(&Vec3::from_point(self).min_components(&Vec3::from_point(other)))
+    }
+
+    /// Component-wise maximum, with the same NaN behavior as
+    /// `Vec3::max_components`.
+    pub fn max_components(&self, other: &Self) -> Self
+    where
+        T: Copy + PartialOrd,
+    {
+        Self::from_vec(&Vec3::from_point(self).max_components(&Vec3::from_point(other)))
+    }
+
+    pub fn abs(&self) -> Self
+    where
+        T: Abs,
+    {
+        Self {
+            x: self.x.abs(),
+            y: self.y.abs(),
+            z: self.z.abs(),
+        }
+    }
+
+    /// `true` when no component is infinite or NaN.
+    pub fn is_finite(&self) -> bool
+    where
+        T: Finite,
+    {
+        self.x.is_finite() && self.y.is_finite() && self.z.is_finite()
+    }
+
+    /// `true` when any component is NaN.
+    pub fn is_nan(&self) -> bool
+    where
+        T: Finite,
+    {
+        self.x.is_nan() || self.y.is_nan() || self.z.is_nan()
+    }
+
+    /// Applies `f` to every component, possibly changing the component type.
+    pub fn map<U>(&self, f: impl Fn(T) -> U) -> Point3<U>
+    where
+        T: Copy,
+    {
+        Point3 {
+            x: f(self.x),
+            y: f(self.y),
+            z: f(self.z),
+        }
+    }
+
+    /// Iterates over the components in x, y, z order.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        [&self.x, &self.y, &self.z].into_iter()
+    }
+
+    pub fn get(&self, i: usize) -> Option<&T> {
+        match i {
+            0 => Some(&self.x),
+            1 => Some(&self.y),
+            2 => Some(&self.z),
+            _ => None,
+        }
+    }
+
+    pub fn get_mut(&mut self, i: usize) -> Option<&mut T> {
+        match i {
+            0 => Some(&mut self.x),
+            1 => Some(&mut self.y),
+            2 => Some(&mut self.z),
+            _ => None,
+        }
+    }
+}
+
+/// Orthonormalizes `a`, `b` and `c` in place: `a` keeps its direction, `b`
+/// loses its component along `a`, and `c` loses its components along both.
+/// The inputs must be linearly independent.
+pub fn gram_schmidt<T>(a: &mut Vec3<T>, b: &mut Vec3<T>, c: &mut Vec3<T>)
+where
+    T: Copy
+        + Default
+        + Zero
+        + One
+        + Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + Div<Output = T>
+        + MulAssign
+        + Sqrt
+        + PartialOrd,
+{
+    a.normalize();
+    *b = *b - b.project_onto(a);
+    b.normalize();
+    *c = *c - c.project_onto(a) - c.project_onto(b);
+    c.normalize();
+}
+
+/// Average of `points`. An empty slice yields the origin rather than an
+/// `Option`, so the result can be used directly as a pivot or center.
+pub fn centroid<T>(points: &[Point3<T>]) -> Point3<T>
+where
+    T: Copy + Default + One + Add<Output = T> + Div<Output = T>,
+{
+    if points.is_empty() {
+        return Point3::zeros();
+    }
+
+    // Counting in `T` avoids needing a conversion from `usize`.
+    let (sum, count) = points
+        .iter()
+        .fold((Point3::zeros(), T::default()), |(sum, count), p| {
+            (sum + *p, count + T::one())
+        });
+    Point3::new(sum.x / count, sum.y / count, sum.z / count)
+}
+
+#[cfg(any(feature = "std", feature = "libm"))]
+impl Sqrt for f32 {
+    #[cfg(feature = "std")]
+    fn sqrt(&self) -> Self {
+        f32::sqrt(*self)
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn sqrt(&self) -> Self {
+        libm::sqrtf(*self)
+    }
+}
+
+#[cfg(any(feature = "std", feature = "libm"))]
+impl Sqrt for f64 {
+    #[cfg(feature = "std")]
+    fn sqrt(&self) -> Self {
+        f64::sqrt(*self)
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn sqrt(&self) -> Self {
+        libm::sqrt(*self)
+    }
+}
+
+/// Digit-by-digit square root, truncated towards zero. Written out rather
+/// than using `u64::isqrt`, which needs Rust 1.84.
+fn integer_sqrt(mut n: u64) -> u64 {
+    if n == 0 {
+        return 0;
+    }
+    // Highest power of four not above `n`.
+    let mut bit = 1u64 << ((63 - n.leading_zeros()) & !1);
+    let mut root = 0;
+    while bit != 0 {
+        if n >= root + bit {
+            n -= root + bit;
+            root = (root >> 1) + bit;
+        } else {
+            root >>= 1;
+        }
+        bit >>= 2;
+    }
+    root
+}
+
+/// Integer square roots are truncated towards zero, e.g. `10.sqrt() == 3`.
+/// Signed types panic on negative input.
+macro_rules! impl_integer_sqrt {
+    (signed: $($t:ty),*) => {
+        $(
+            impl Sqrt for $t {
+                fn sqrt(&self) -> Self {
+                    assert!(*self >= 0, "square root of a negative integer");
+                    integer_sqrt(*self as u64) as $t
+                }
+            }
+        )*
+    };
+    (unsigned: $($t:ty),*) => {
+        $(
+            impl Sqrt for $t {
+                fn sqrt(&self) -> Self {
+                    integer_sqrt(*self as u64) as $t
+                }
+            }
+        )*
+    };
+}
+
+impl_integer_sqrt!(signed: i32, i64);
+impl_integer_sqrt!(unsigned: u32, u64);
+
+impl Abs for f32 {
+    #[cfg(feature = "std")]
+    fn abs(&self) -> Self {
+        f32::abs(*self)
+    }
+
+    // Clearing the sign bit needs no libm, which keeps `ApproxEq` available
+    // in every configuration.
+    #[cfg(not(feature = "std"))]
+    fn abs(&self) -> Self {
+        f32::from_bits(self.to_bits() & !(1 << (u32::BITS - 1)))
+    }
+}
+
+impl Abs for f64 {
+    #[cfg(feature = "std")]
+    fn abs(&self) -> Self {
+        f64::abs(*self)
+    }
+
+    // Clearing the sign bit needs no libm, which keeps `ApproxEq` available
+    // in every configuration.
+    #[cfg(not(feature = "std"))]
+    fn abs(&self) -> Self {
+        f64::from_bits(self.to_bits() & !(1 << (u64::BITS - 1)))
+    }
+}
+
+#[cfg(any(feature = "std", feature = "libm"))]
+impl Trig for f32 {
+    #[cfg(feature = "std")]
+    fn sin(&self) -> Self {
+        f32::sin(*self)
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn sin(&self) -> Self {
+        libm::sinf(*self)
+    }
+
+    #[cfg(feature = "std")]
+    fn cos(&self) -> Self {
+        f32::cos(*self)
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn cos(&self) -> Self {
+        libm::cosf(*self)
+    }
+
+    #[cfg(feature = "std")]
+    fn tan(&self) -> Self {
+        f32::tan(*self)
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn tan(&self) -> Self {
+        libm::tanf(*self)
+    }
+}
+
+#[cfg(any(feature = "std", feature = "libm"))]
+impl Trig for f64 {
+    #[cfg(feature = "std")]
+    fn sin(&self) -> Self {
+        f64::sin(*self)
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn sin(&self) -> Self {
+        libm::sin(*self)
+    }
+
+    #[cfg(feature = "std")]
+    fn cos(&self) -> Self {
+        f64::cos(*self)
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn cos(&self) -> Self {
+        libm::cos(*self)
+    }
+
+    #[cfg(feature = "std")]
+    fn tan(&self) -> Self {
+        f64::tan(*self)
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn tan(&self) -> Self {
+        libm::tan(*self)
+    }
+}
+
+#[cfg(any(feature = "std", feature = "libm"))]
+impl Acos for f32 {
+    #[cfg(feature = "std")]
+    fn acos(&self) -> Self {
+        f32::acos(*self)
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn acos(&self) -> Self {
+        libm::acosf(*self)
+    }
+}
+
+#[cfg(any(feature = "std", feature = "libm"))]
+impl Acos for f64 {
+    #[cfg(feature = "std")]
+    fn acos(&self) -> Self {
+        f64::acos(*self)
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn acos(&self) -> Self {
+        libm::acos(*self)
+    }
+}
+
+#[cfg(any(feature = "std", feature = "libm"))]
+impl Atan2 for f32 {
+    #[cfg(feature = "std")]
+    fn atan2(&self, x: &Self) -> Self {
+        f32::atan2(*self, *x)
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn atan2(&self, x: &Self) -> Self {
+        libm::atan2f(*self, *x)
+    }
+}
+
+#[cfg(any(feature = "std", feature = "libm"))]
+impl Atan2 for f64 {
+    #[cfg(feature = "std")]
+    fn atan2(&self, x: &Self) -> Self {
+        f64::atan2(*self, *x)
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn atan2(&self, x: &Self) -> Self {
+        libm::atan2(*self, *x)
+    }
+}
+
+#[cfg(any(feature = "std", feature = "libm"))]
+impl Round for f32 {
+    #[cfg(feature = "std")]
+    fn floor(&self) -> Self {
+        f32::floor(*self)
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn floor(&self) -> Self {
+        libm::floorf(*self)
+    }
+
+    #[cfg(feature = "std")]
+    fn ceil(&self) -> Self {
+        f32::ceil(*self)
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn ceil(&self) -> Self {
+        libm::ceilf(*self)
+    }
+
+    #[cfg(feature = "std")]
+    fn round(&self) -> Self {
+        f32::round(*self)
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn round(&self) -> Self {
+        libm::roundf(*self)
+    }
+
+    #[cfg(feature = "std")]
+    fn fract(&self) -> Self {
+        f32::fract(*self)
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn fract(&self) -> Self {
+        *self - libm::truncf(*self)
+    }
+}
+
+#[cfg(any(feature = "std", feature = "libm"))]
+impl Round for f64 {
+    #[cfg(feature = "std")]
+    fn floor(&self) -> Self {
+        f64::floor(*self)
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn floor(&self) -> Self {
+        libm::floor(*self)
+    }
+
+    #[cfg(feature = "std")]
+    fn ceil(&self) -> Self {
+        f64::ceil(*self)
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn ceil(&self) -> Self {
+        libm::ceil(*self)
+    }
+
+    #[cfg(feature = "std")]
+    fn round(&self) -> Self {
+        f64::round(*self)
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn round(&self) -> Self {
+        libm::round(*self)
+    }
+
+    #[cfg(feature = "std")]
+    fn fract(&self) -> Self {
+        f64::fract(*self)
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn fract(&self) -> Self {
+        *self - libm::trunc(*self)
+    }
+}
+
+macro_rules! impl_finite {
+    ($($t:ty),*) => {
+        $(
+            impl Finite for $t {
+                fn is_finite(&self) -> bool {
+                    <$t>::is_finite(*self)
+                }
+
+                fn is_nan(&self) -> bool {
+                    <$t>::is_nan(*self)
+                }
+            }
+        )*
+    };
+}
+
+impl_finite!(f32, f64);
+
+macro_rules! impl_bit_eq {
+    ($($t:ty),*) => {
+        $(
+            impl BitEq for $t {
+                fn bit_eq(&self, other: &Self) -> bool {
+                    self.to_bits() == other.to_bits()
+                }
+            }
+        )*
+    };
+}
+
+impl_bit_eq!(f32, f64);
+
+impl ApproxEq<f32> for f32 {
+    fn approx_eq(&self, other: &Self, epsilon: f32) -> bool {
+        Abs::abs(&(self - other)) <= epsilon
+    }
+}
+
+impl ApproxEq<f64> for f64 {
+    fn approx_eq(&self, other: &Self, epsilon: f64) -> bool {
+        Abs::abs(&(self - other)) <= epsilon
+    }
+}
+
+impl<T: Copy + ApproxEq<T>> ApproxEq<T> for Vec3<T> {
+    fn approx_eq(&self, other: &Self, epsilon: T) -> bool {
+        self.x.approx_eq(&other.x, epsilon)
+            && self.y.approx_eq(&other.y, epsilon)
+            && self.z.approx_eq(&other.z, epsilon)
+    }
+}
+
+impl<T: Copy + ApproxEq<T>> ApproxEq<T> for Point3<T> {
+    fn approx_eq(&self, other: &Self, epsilon: T) -> bool {
+        self.x.approx_eq(&other.x, epsilon)
+            && self.y.approx_eq(&other.y, epsilon)
+            && self.z.approx_eq(&other.z, epsilon)
+    }
+}
+
+impl Mix<f32> for f32 {
+    fn mix(&self, other: &Self, t: f32) -> Self {
+        self + (other - self) * t
+    }
+}
+
+impl Mix<f64> for f64 {
+    fn mix(&self, other: &Self, t: f64) -> Self {
+        self + (other - self) * t
+    }
+}
+
+impl<T> Mix<T> for Vec3<T>
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    fn mix(&self, other: &Self, t: T) -> Self {
+        Vec3::lerp(self, other, t)
+    }
+}
+
+impl<T> Mix<T> for Point3<T>
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    fn mix(&self, other: &Self, t: T) -> Self {
+        Point3::lerp(self, other, t)
+    }
+}
+
+macro_rules! impl_zero {
+    ($($t:ty => $zero:expr),*) => {
+        $(
+            impl Zero for $t {
+                fn zero() -> Self {
+                    $zero
+                }
+            }
+        )*
+    };
+}
+
+impl_zero!(
+    f32 => 0.0, f64 => 0.0,
+    i8 => 0, i16 => 0, i32 => 0, i64 => 0, i128 => 0, isize => 0,
+    u8 => 0, u16 => 0, u32 => 0, u64 => 0, u128 => 0, usize => 0
+);
+
+macro_rules! impl_one {
+    ($($t:ty => $one:expr),*) => {
+        $(
+            impl One for $t {
+                fn one() -> Self {
+                    $one
+                }
+            }
+        )*
+    };
+}
+
+impl_one!(
+    f32 => 1.0, f64 => 1.0,
+    i8 => 1, i16 => 1, i32 => 1, i64 => 1, i128 => 1, isize => 1,
+    u8 => 1, u16 => 1, u32 => 1, u64 => 1, u128 => 1, usize => 1
+);
+
+impl Pi for f32 {
+    fn pi() -> Self {
+        core::f32::consts::PI
+    }
+}
+
+impl Pi for f64 {
+    fn pi() -> Self {
+        core::f64::consts::PI
+    }
+}
+
+impl FromF64 for f32 {
+    fn from_f64(value: f64) -> Self {
+        value as f32
+    }
+}
+
+impl FromF64 for f64 {
+    fn from_f64(value: f64) -> Self {
+        value
+    }
+}
+
+macro_rules! impl_epsilon {
+    ($($t:ty => $eps:expr),*) => {
+        $(
+            impl Epsilon for $t {
+                fn epsilon() -> Self {
+                    $eps
+                }
+            }
+        )*
+    };
+}
+
+impl_epsilon!(f32 => f32::EPSILON, f64 => f64::EPSILON);
+
+macro_rules! impl_checked_arith {
+    ($($t:ty),*) => {
+        $(
+            impl CheckedArith for $t {
+                fn checked_add(&self, rhs: &Self) -> Option<Self> {
+                    <$t>::checked_add(*self, *rhs)
+                }
+
+                fn checked_mul(&self, rhs: &Self) -> Option<Self> {
+                    <$t>::checked_mul(*self, *rhs)
+                }
+            }
+        )*
+    };
+}
+
+impl_checked_arith!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+impl<T> Vector<T> for Vec3<T> {
+    fn dot(lhs: &Self, rhs: &Self) -> T
+    where
+        T: Copy + Add<Output = T> + Mul<Output = T>,
+    {
+        lhs.x * rhs.x + lhs.y * rhs.y + lhs.z * rhs.z
+    }
+
+    fn normalize(&mut self)
+    where
+        T: Copy
+            + Add<Output = T>
+            + Mul<Output = T>
+            + Div<Output = T>
+            + MulAssign
+            + Zero
+            + One
+            + Sqrt
+            + PartialOrd,
+    {
+        Self::normalize(self)
+    }
+}
+
+impl<T> Vector3<T> for Vec3<T> {
+    fn cross(&self, other: &Self) -> Self
+    where
+        T: Copy + Add<Output = T> + Mul<Output = T> + Sub<Output = T>,
+    {
+        Self::cross(self, other)
+    }
+}
+
+impl<T: Add<Output = T>> Add for Vec3<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self {
+            x: self.x + rhs.x,
+            y: self.y + rhs.y,
+            z: self.z + rhs.z,
+        }
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Vec3<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y,
+            z: self.z - rhs.z,
+        }
+    }
+}
+
+impl<T: AddAssign> AddAssign for Vec3<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+        self.z += rhs.z;
+    }
+}
+
+impl<T: SubAssign> SubAssign for Vec3<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+        self.z -= rhs.z;
+    }
+}
+
+/// `Vec3 * Vec3` is the cross product. See `Vec3 * T` for scaling.
+impl<T: Copy + Add<Output = T> + Mul<Output = T> + Sub<Output = T>> Mul for Vec3<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.cross(&rhs)
+    }
+}
+
+/// `Vec3 * T` scales every component. Not to be confused with `Vec3 * Vec3`,
+/// which is the cross product.
+impl<T: Copy + Mul<Output = T>> Mul<T> for Vec3<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        Self {
+            x: self.x * rhs,
+            y: self.y * rhs,
+            z: self.z * rhs,
+        }
+    }
+}
+
+impl<T: Copy + MulAssign> MulAssign<T> for Vec3<T> {
+    fn mul_assign(&mut self, rhs: T) {
+        self.x *= rhs;
+        self.y *= rhs;
+        self.z *= rhs;
+    }
+}
+
+/// `Vec3 / T` divides every component directly rather than multiplying by
+/// the reciprocal, so the result is exactly rounded.
+impl<T: Copy + Div<Output = T>> Div<T> for Vec3<T> {
+    type Output = Self;
+
+    fn div(self, rhs: T) -> Self::Output {
+        Self {
+            x: self.x / rhs,
+            y: self.y / rhs,
+            z: self.z / rhs,
+        }
+    }
+}
+
+impl<T: Copy + DivAssign> DivAssign<T> for Vec3<T> {
+    fn div_assign(&mut self, rhs: T) {
+        self.x /= rhs;
+        self.y /= rhs;
+        self.z /= rhs;
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Vec3<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            x: -self.x,
+            y: -self.y,
+            z: -self.z,
+        }
+    }
+}
+
+/// Sums the vectors component-wise, starting from `Vec3::zeros()`.
+impl<T: Default + Add<Output = T>> Sum for Vec3<T> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::default(), |acc, v| acc + v)
+    }
+}
+
+/// Multiplies the vectors component-wise, starting from `Vec3::one()`. Unlike
+/// `Vec3 * Vec3` this is not the cross product.
+impl<T: Copy + One + Mul<Output = T>> Product for Vec3<T> {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::one(), |acc, v| acc.component_mul(&v))
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point3<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self {
+            x: self.x + rhs.x,
+            y: self.y + rhs.y,
+            z: self.z + rhs.z,
+        }
+    }
+}
+
+/// The difference of two points is the displacement vector between them.
+impl<T: Sub<Output = T>> Sub for Point3<T> {
+    type Output = Vec3<T>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Vec3 {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y,
+            z: self.z - rhs.z,
+        }
+    }
+}
+
+impl<T: Add<Output = T>> Add<Vec3<T>> for Point3<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Vec3<T>) -> Self::Output {
+        Self {
+            x: self.x + rhs.x,
+            y: self.y + rhs.y,
+            z: self.z + rhs.z,
+        }
+    }
+}
+
+impl<T: Sub<Output = T>> Sub<Vec3<T>> for Point3<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Vec3<T>) -> Self::Output {
+        Self {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y,
+            z: self.z - rhs.z,
+        }
+    }
+}
+
+impl<T: AddAssign> AddAssign for Point3<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+        self.z += rhs.z;
+    }
+}
+
+impl<T: SubAssign> SubAssign for Point3<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+        self.z -= rhs.z;
+    }
+}
+
+impl<T: Copy + MulAssign> MulAssign<T> for Point3<T> {
+    fn mul_assign(&mut self, rhs: T) {
+        self.x *= rhs;
+        self.y *= rhs;
+        self.z *= rhs;
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point3<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            x: -self.x,
+            y: -self.y,
+            z: -self.z,
+        }
+    }
+}
+
+impl<T> Point<T> for Point3<T> {
+    fn distance_from_origin(&self) -> T
+    where
+        T: Copy + Add<Output = T> + Mul<Output = T> + Sqrt,
+    {
+        let value = self.x * self.x + self.y * self.y + self.z * self.z;
+        value.sqrt()
+    }
+
+    fn distance(&self, other: &Self) -> T
+    where
+        T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Sqrt,
+    {
+        (*self - *other).magnitude()
+    }
+
+    fn distance_squared(&self, other: &Self) -> T
+    where
+        T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+    {
+        (*self - *other).magnitude_squared()
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Vec3<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match f.precision() {
+            Some(p) => write!(f, "({:.*}, {:.*}, {:.*})", p, self.x, p, self.y, p, self.z),
+            None => write!(f, "({}, {}, {})", self.x, self.y, self.z),
+        }
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Point3<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match f.precision() {
+            Some(p) => write!(f, "({:.*}, {:.*}, {:.*})", p, self.x, p, self.y, p, self.z),
+            None => write!(f, "({}, {}, {})", self.x, self.y, self.z),
+        }
+    }
+}
+
+impl<T> Index<usize> for Vec3<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        match index {
+            0 => &self.x,
+            1 => &self.y,
+            2 => &self.z,
+            _ => panic!("Out of bound access in Vec3<T>!"),
+        }
+    }
+}
+
+impl<T> IndexMut<usize> for Vec3<T> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        match index {
+            0 => &mut self.x,
+            1 => &mut self.y,
+            2 => &mut self.z,
+            _ => panic!("Out of bound access in Vec3<T>!"),
+        }
+    }
+}
+
+impl<T> Index<usize> for Point3<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        match index {
+            0 => &self.x,
+            1 => &self.y,
+            2 => &self.z,
+            _ => panic!("Out of bound access in Point3<T>!"),
+        }
+    }
+}
+
+impl<T> IndexMut<usize> for Point3<T> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        match index {
+            0 => &mut self.x,
+            1 => &mut self.y,
+            2 => &mut self.z,
+            _ => panic!("Out of bound access in Point3<T>!"),
+        }
+    }
+}
+
+impl<T> From<[T; 3]> for Vec3<T> {
+    fn from(a: [T; 3]) -> Self {
+        let [x, y, z] = a;
+        Self { x, y, z }
+    }
+}
+
+impl<T> From<Vec3<T>> for [T; 3] {
+    fn from(v: Vec3<T>) -> Self {
+        [v.x, v.y, v.z]
+    }
+}
+
+impl<T> From<[T; 3]> for Point3<T> {
+    fn from(a: [T; 3]) -> Self {
+        let [x, y, z] = a;
+        Self { x, y, z }
+    }
+}
+
+impl<T> From<Point3<T>> for [T; 3] {
+    fn from(v: Point3<T>) -> Self {
+        [v.x, v.y, v.z]
+    }
+}
+
+impl<T> IntoIterator for Vec3<T> {
+    type Item = T;
+    type IntoIter = core::array::IntoIter<T, 3>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        [self.x, self.y, self.z].into_iter()
+    }
+}
+
+impl<T> IntoIterator for Point3<T> {
+    type Item = T;
+    type IntoIter = core::array::IntoIter<T, 3>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        [self.x, self.y, self.z].into_iter()
+    }
+}
+
+pub type Vec3d = Vec3<f64>;
+pub type Vec3f = Vec3<f32>;
+
+pub type Point3d = Point3<f64>;
+pub type Point3f = Point3<f32>;
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        centroid, gram_schmidt, ApproxEq, Mix, One, Point, Point3, Sqrt, Tuple, Vec3, Vector,
+        Vector3, Zero,
+    };
+    use crate::{Tuple2, Vec2, Vec4};
+    use std::f64::consts::FRAC_1_SQRT_2;
+
+    #[macro_export]
+    macro_rules! assert_approx_eq {
+        ($a:expr, $b:expr) => {{
+            let eps = 1.0e-6;
+            let (a, b) = (&$a, &$b);
+            assert!(
+                (*a - *b).abs() < eps,
+                "assertion failed: `(left !== right)` \
+                 (left: `{:?}`, right: `{:?}`, expect diff: `{:?}`, real diff: `{:?}`)",
+                *a,
+                *b,
+                eps,
+                (*a - *b).abs()
+            );
+        }};
+        ($a:expr, $b:expr, $eps:expr) => {{
+            let (a, b) = (&$a, &$b);
+            let eps = $eps;
+            assert!(
+                (*a - *b).abs() < eps,
+                "assertion failed: `(left !== right)` \
+                 (left: `{:?}`, right: `{:?}`, expect diff: `{:?}`, real diff: `{:?}`)",
+                *a,
+                *b,
+                eps,
+                (*a - *b).abs()
+            );
+        }};
+    }
+
+    #[test]
+    fn create_and_modify_vector() {
+        let zeros: Vec3<f64> = Vec3::zeros();
+        assert_eq!(zeros.x, 0.0);
+        assert_eq!(zeros[0], 0.0);
+        assert_eq!(zeros.y, 0.0);
+        assert_eq!(zeros[1], 0.0);
+        assert_eq!(zeros.z, 0.0);
+        assert_eq!(zeros[2], 0.0);
+
+        let vec: Vec3<f64> = Vec3::new(1.1, 2.2, 3.3);
+        assert_eq!(vec.x, 1.1);
+        assert_eq!(vec.y, 2.2);
+        assert_eq!(vec.z, 3.3);
+
+        let mut vec: Vec3<f64> = Vec3::zeros();
+        vec[0] = 1.1;
+        vec[1] = 2.2;
+        vec[2] = 3.3;
+        assert_eq!(vec[0], 1.1);
+        assert_eq!(vec[1], 2.2);
+        assert_eq!(vec[2], 3.3);
+    }
+
+    #[test]
+    fn create_point() {
+        let zeros: Point3<f64> = Point3::zeros();
+        assert_eq!(zeros.x, 0.0);
+        assert_eq!(zeros.y, 0.0);
+        assert_eq!(zeros.z, 0.0);
+
+        let vec: Point3<f64> = Point3::new(1.1, 2.2, 3.3);
+        assert_eq!(vec.x, 1.1);
+        assert_eq!(vec.y, 2.2);
+        assert_eq!(vec.z, 3.3);
+    }
+
+    #[test]
+    fn generic_sqrt() {
+        let vec: Vec3<f64> = Vec3::new(4.0, 49.0, 64.0);
+        assert_eq!(vec.x.sqrt(), 2.0);
+        assert_eq!(vec.y.sqrt(), 7.0);
+        assert_eq!(vec.z.sqrt(), 8.0);
+
+        let vec: Vec3<f32> = Vec3::new(4.0, 49.0, 64.0);
+        assert_eq!(vec.x.sqrt(), 2.0);
+        assert_eq!(vec.y.sqrt(), 7.0);
+        assert_eq!(vec.z.sqrt(), 8.0);
+    }
+
+    #[test]
+    fn dot_product() {
+        let lhs: Vec3<f64> = Vec3::new(3.1, 5.0, -2.0);
+        let rhs: Vec3<f64> = Vec3::new(11.27, -9.0, 0.0);
+        let dot = Vec3::dot(&lhs, &rhs);
+
+        assert_approx_eq!(dot, -10.063, 1e-12);
+        assert_approx_eq!(lhs.dot(&rhs), -10.063, 1e-12);
+    }
+
+    #[test]
+    fn cross_product() {
+        let lhs: Vec3<f64> = Vec3::new(3.1, 5.0, -2.0);
+        let rhs: Vec3<f64> = Vec3::new(11.27, -9.0, 0.0);
+        let vec = Vec3::cross(&lhs, &rhs);
+        assert_eq!(vec.x, -18.0);
+        assert_eq!(vec.y, -22.54);
+        assert_eq!(vec.z, -84.25);
+
+        let vec = lhs * rhs;
+        assert_eq!(vec.x, -18.0);
+        assert_eq!(vec.y, -22.54);
+        assert_eq!(vec.z, -84.25);
+    }
+
+    #[test]
+    fn vec_magnitude() {
+        let vec: Vec3<f64> = Vec3::new(2.0, 0.0, 0.0);
+        assert_eq!(vec.magnitude(), 2.0);
+    }
+
+    #[test]
+    fn basic_vector_ops() {
+        let lhs: Vec3<f64> = Vec3::new(1.0, 2.0, 3.0);
+        let rhs: Vec3<f64> = Vec3::new(4.0, 5.0, 6.0);
+        let sum = lhs + rhs;
+        let diff = lhs - rhs;
+
+        assert_eq!(sum.x, 5.0);
+        assert_eq!(sum.y, 7.0);
+        assert_eq!(sum.z, 9.0);
+
+        assert_eq!(diff.x, -3.0);
+        assert_eq!(diff.y, -3.0);
+        assert_eq!(diff.z, -3.0);
+    }
+
+    #[test]
+    fn normalize_vector_and_point() {
+        let mut vec: Vec3<f64> = Vec3::new(1.0, 2.0, 3.0);
+        vec.normalize();
+        assert_approx_eq!(vec.x, 0.2672612419124244, 1e-12);
+        assert_approx_eq!(vec.y, 0.5345224838248488, 1e-12);
+        assert_approx_eq!(vec.z, 0.8017837257372732, 1e-12);
+
+        let mut point: Point3<f64> = Point3::new(1.0, 2.0, 3.0);
+        point.normalize();
+        assert_approx_eq!(point.x, 0.2672612419124244, 1e-12);
+        assert_approx_eq!(point.y, 0.5345224838248488, 1e-12);
+        assert_approx_eq!(point.z, 0.8017837257372732, 1e-12);
+    }
+
+    #[test]
+    fn splat_constructor() {
+        let vec: Vec3<f64> = Vec3::splat(1.0);
+        assert_eq!(vec, Vec3::new(1.0, 1.0, 1.0));
+
+        let point = Point3::splat(-3);
+        assert_eq!(point, Point3::new(-3, -3, -3));
+    }
+
+    #[test]
+    fn unit_axes() {
+        let (x, y, z): (Vec3<f64>, _, _) = (Vec3::unit_x(), Vec3::unit_y(), Vec3::unit_z());
+        assert_eq!(x, Vec3::new(1.0, 0.0, 0.0));
+        assert_eq!(y, Vec3::new(0.0, 1.0, 0.0));
+        assert_eq!(z, Vec3::new(0.0, 0.0, 1.0));
+        assert_eq!(x.cross(&y), z);
+        assert_eq!(y.cross(&z), x);
+        assert_eq!(z.cross(&x), y);
+
+        assert_eq!(Vec3::<i32>::zero(), Vec3::new(0, 0, 0));
+        assert_eq!(Vec3::<i32>::one(), Vec3::new(1, 1, 1));
+        assert_eq!(x + y + z, Vec3::one());
+    }
+
+    #[test]
+    fn orthonormal_basis_from_normal() {
+        let normals: [Vec3<f64>; 6] = [
+            Vec3::new(0.0, 0.0, 1.0),
+            Vec3::new(0.0, 0.0, -1.0),
+            Vec3::new(1.0, 0.0, 0.0),
+            Vec3::new(0.3, -0.8, 0.1).normalized(),
+            Vec3::new(-2.0, 1.0, -5.0).normalized(),
+            Vec3::new(1e-9, 0.0, -1.0).normalized(),
+        ];
+        for n in normals {
+            let (t, b) = n.orthonormal_basis();
+            assert_approx_eq!(t.magnitude(), 1.0, 1e-12);
+            assert_approx_eq!(b.magnitude(), 1.0, 1e-12);
+            assert_approx_eq!(t.dot(&b), 0.0, 1e-12);
+            assert_approx_eq!(t.dot(&n), 0.0, 1e-12);
+            assert_approx_eq!(b.dot(&n), 0.0, 1e-12);
+            assert!(t.cross(&b).approx_eq(&n, 1e-12));
+        }
+    }
+
+    fn cosine<V: Vector<f64>>(a: &V, b: &V) -> f64 {
+        V::dot(a, b) / (a.magnitude() * b.magnitude())
+    }
+
+    fn unit<V: Vector<f64> + Copy>(v: &V) -> V {
+        let mut result = *v;
+        result.normalize();
+        result
+    }
+
+    #[test]
+    fn generic_over_vector_sizes() {
+        let (a, b) = (Vec2::new(2.0, 0.0), Vec2::new(3.0, 3.0));
+        assert_approx_eq!(cosine(&a, &b), FRAC_1_SQRT_2, 1e-12);
+        assert_approx_eq!(unit(&b).magnitude(), 1.0, 1e-12);
+
+        let (a, b) = (Vec3::new(0.0, 2.0, 0.0), Vec3::new(0.0, 3.0, 3.0));
+        assert_approx_eq!(cosine(&a, &b), FRAC_1_SQRT_2, 1e-12);
+        assert_approx_eq!(unit(&b).magnitude(), 1.0, 1e-12);
+
+        let (a, b) = (Vec4::new(0.0, 0.0, 0.0, 2.0), Vec4::new(3.0, 0.0, 0.0, 3.0));
+        assert_approx_eq!(cosine(&a, &b), FRAC_1_SQRT_2, 1e-12);
+        assert_approx_eq!(unit(&b).magnitude(), 1.0, 1e-12);
+
+        assert_eq!(cosine(&a, &Vec4::new(0.0, 1.0, 0.0, 0.0)), 0.0);
+    }
+
+    fn unit_normal<V: Vector3<f64>>(a: &V, b: &V) -> V {
+        let mut n = a.cross(b);
+        n.normalize();
+        n
+    }
+
+    fn unit_tuple<P: Tuple<f64> + Copy>(p: &P) -> P {
+        let mut result = *p;
+        result.normalize();
+        result
+    }
+
+    #[test]
+    fn generic_cross_and_tuple_normalize() {
+        let n = unit_normal(&Vec3::new(2.0, 0.0, 0.0), &Vec3::new(0.0, 3.0, 0.0));
+        assert_eq!(n, Vec3::new(0.0, 0.0, 1.0));
+
+        assert!(unit_tuple(&Vec3::new(0.0, 3.0, 4.0)).approx_eq(&Vec3::new(0.0, 0.6, 0.8), 1e-12));
+        assert_eq!(
+            unit_tuple(&Point3::new(0.0, 0.0, -2.0)),
+            Point3::new(0.0, 0.0, -1.0)
+        );
+    }
+
+    #[test]
+    fn gram_schmidt_orthonormalizes() {
+        let mut a: Vec3<f64> = Vec3::new(1.0, 0.01, -0.02);
+        let mut b: Vec3<f64> = Vec3::new(0.03, 2.0, 0.01);
+        let mut c: Vec3<f64> = Vec3::new(-0.01, 0.02, 0.5);
+        let original_a = a;
+        gram_schmidt(&mut a, &mut b, &mut c);
+
+        for v in [a, b, c] {
+            assert_approx_eq!(v.magnitude(), 1.0, 1e-12);
+        }
+        assert_approx_eq!(a.dot(&b), 0.0, 1e-12);
+        assert_approx_eq!(a.dot(&c), 0.0, 1e-12);
+        assert_approx_eq!(b.dot(&c), 0.0, 1e-12);
+        assert!(a.approx_eq(&original_a.normalized(), 1e-12));
+        assert!(a.cross(&b).approx_eq(&c, 1e-12));
+    }
+
+    #[test]
+    fn centroid_of_points() {
+        let square: [Point3<f64>; 4] = [
+            Point3::new(0.0, 0.0, 1.0),
+            Point3::new(2.0, 0.0, 1.0),
+            Point3::new(2.0, 2.0, 1.0),
+            Point3::new(0.0, 2.0, 1.0),
+        ];
+        assert_eq!(centroid(&square), Point3::new(1.0, 1.0, 1.0));
+        assert_eq!(centroid(&square[..1]), square[0]);
+        assert_eq!(centroid::<f64>(&[]), Point3::zeros());
+    }
+
+    #[test]
+    fn barycentric_combination() {
+        let a: Point3<f64> = Point3::new(0.0, 0.0, 0.0);
+        let b = Point3::new(3.0, 0.0, 0.0);
+        let c = Point3::new(0.0, 3.0, 3.0);
+
+        assert_eq!(Point3::barycentric(&a, &b, &c, 1.0, 0.0, 0.0), a);
+        assert_eq!(Point3::barycentric(&a, &b, &c, 0.0, 1.0, 0.0), b);
+        assert_eq!(Point3::barycentric_uv(&a, &b, &c, 0.0, 0.0), c);
+
+        let third = 1.0 / 3.0;
+        let center = Point3::barycentric(&a, &b, &c, third, third, third);
+        assert!(center.approx_eq(&centroid(&[a, b, c]), 1e-12));
+        let center = Point3::barycentric_uv(&a, &b, &c, third, third);
+        assert!(center.approx_eq(&centroid(&[a, b, c]), 1e-12));
+    }
+
+    #[test]
+    fn catmull_rom_segment() {
+        let p: [Point3<f64>; 5] = [
+            Point3::new(0.0, 0.0, 0.0),
+            Point3::new(1.0, 2.0, 0.0),
+            Point3::new(3.0, 3.0, 1.0),
+            Point3::new(4.0, 1.0, 2.0),
+            Point3::new(6.0, 0.0, 2.0),
+        ];
+        assert_eq!(Point3::catmull_rom(&p[0], &p[1], &p[2], &p[3], 0.0), p[1]);
+        assert_eq!(Point3::catmull_rom(&p[0], &p[1], &p[2], &p[3], 1.0), p[2]);
+
+        // Evenly spaced collinear points are interpolated linearly.
+        let line = [0.0, 1.0, 2.0, 3.0].map(|x| Point3::new(x, 2.0 * x, -x));
+        let mid = Point3::catmull_rom(&line[0], &line[1], &line[2], &line[3], 0.5);
+        assert!(mid.approx_eq(&Point3::new(1.5, 3.0, -1.5), 1e-12));
+
+        // Adjacent segments meet at p[2] with the same tangent.
+        let h = 1e-6;
+        let before = Point3::catmull_rom(&p[0], &p[1], &p[2], &p[3], 1.0 - h);
+        let after = Point3::catmull_rom(&p[1], &p[2], &p[3], &p[4], h);
+        let tangent = (p[3] - p[1]) * 0.5;
+        assert!(((p[2] - before) / h).approx_eq(&tangent, 1e-5));
+        assert!(((after - p[2]) / h).approx_eq(&tangent, 1e-5));
+    }
+
+    #[test]
+    fn bezier_curve() {
+        let p0: Point3<f64> = Point3::new(0.0, 0.0, 0.0);
+        let p1 = Point3::new(1.0, 2.0, 0.0);
+        let p2 = Point3::new(3.0, 2.0, 1.0);
+        let p3 = Point3::new(4.0, 0.0, 2.0);
+        assert_eq!(Point3::bezier(&p0, &p1, &p2, &p3, 0.0), p0);
+        assert_eq!(Point3::bezier(&p0, &p1, &p2, &p3, 1.0), p3);
+
+        // Bernstein form at t = 0.5: (p0 + 3 p1 + 3 p2 + p3) / 8.
+        let mid = Point3::bezier(&p0, &p1, &p2, &p3, 0.5);
+        assert!(mid.approx_eq(&Point3::new(2.0, 1.5, 0.625), 1e-12));
+
+        let start = Point3::bezier_derivative(&p0, &p1, &p2, &p3, 0.0);
+        assert_eq!(start, (p1 - p0) * 3.0);
+        let end = Point3::bezier_derivative(&p0, &p1, &p2, &p3, 1.0);
+        assert_eq!(end, (p3 - p2) * 3.0);
+
+        let h = 1e-6;
+        let ahead = Point3::bezier(&p0, &p1, &p2, &p3, 0.3 + h);
+        let behind = Point3::bezier(&p0, &p1, &p2, &p3, 0.3 - h);
+        let tangent = Point3::bezier_derivative(&p0, &p1, &p2, &p3, 0.3);
+        assert!(((ahead - behind) / (2.0 * h)).approx_eq(&tangent, 1e-6));
+
+        let v = Vec3::bezier(
+            &Vec3::from_point(&p0),
+            &Vec3::from_point(&p1),
+            &Vec3::from_point(&p2),
+            &Vec3::from_point(&p3),
+            0.5,
+        );
+        assert_eq!(v, Vec3::from_point(&mid));
+    }
+
+    #[test]
+    fn triangle_normal_and_area() {
+        let a: Point3<f64> = Point3::new(0.0, 0.0, 0.0);
+        let b = Point3::new(1.0, 0.0, 0.0);
+        let c = Point3::new(0.0, 1.0, 0.0);
+        assert_eq!(Vec3::triangle_normal(&a, &b, &c), Vec3::new(0.0, 0.0, 1.0));
+        assert_eq!(Vec3::triangle_normal(&a, &c, &b), Vec3::new(0.0, 0.0, -1.0));
+        assert_eq!(Vec3::triangle_area(&a, &b, &c), 0.5);
+
+        let a = Point3::new(1.0, -2.0, 3.0);
+        let b = Point3::new(4.0, -2.0, 3.0);
+        let c = Point3::new(1.0, 2.0, 3.0);
+        assert_eq!(Vec3::triangle_normal(&a, &b, &c), Vec3::new(0.0, 0.0, 12.0));
+        assert_eq!(Vec3::triangle_area(&a, &b, &c), 6.0);
+
+        assert_eq!(Vec3::triangle_area(&a, &b, &Point3::lerp(&a, &b, 0.5)), 0.0);
+    }
+
+    #[test]
+    fn triple_products() {
+        let a = Vec3::new(2, -1, 3);
+        let b = Vec3::new(0, 4, -2);
+        let c = Vec3::new(5, 1, 1);
+
+        let det = a.x * (b.y * c.z - b.z * c.y) - a.y * (b.x * c.z - b.z * c.x)
+            + a.z * (b.x * c.y - b.y * c.x);
+        assert_eq!(Vec3::scalar_triple(&a, &b, &c), det);
+        assert_eq!(Vec3::scalar_triple(&a, &b, &c), -38);
+        assert_eq!(Vec3::scalar_triple(&b, &c, &a), det);
+        assert_eq!(Vec3::scalar_triple(&a, &a, &c), 0);
+
+        // BAC-CAB rule: a x (b x c) = b (a . c) - c (a . b)
+        let expected = b * Vec3::dot(&a, &c) - c * Vec3::dot(&a, &b);
+        assert_eq!(Vec3::vector_triple(&a, &b, &c), expected);
+    }
+
+    #[test]
+    fn component_rounding() {
+        let vec: Vec3<f64> = Vec3::new(1.7, -1.2, 2.5);
+        assert_eq!(vec.floor(), Vec3::new(1.0, -2.0, 2.0));
+        assert_eq!(vec.ceil(), Vec3::new(2.0, -1.0, 3.0));
+        assert_eq!(vec.round(), Vec3::new(2.0, -1.0, 3.0));
+        assert!(vec.fract().approx_eq(&Vec3::new(0.7, -0.2, 0.5), 1e-12));
+
+        let vec: Vec3<f32> = Vec3::new(-0.5, 3.25, -7.75);
+        assert_eq!(vec.floor(), Vec3::new(-1.0, 3.0, -8.0));
+        assert_eq!(vec.round(), Vec3::new(-1.0, 3.0, -8.0));
+        assert_eq!(vec.fract(), Vec3::new(-0.5, 0.25, -0.75));
+    }
+
+    #[test]
+    fn finite_and_nan() {
+        let vec: Vec3<f64> = Vec3::new(1.0, -2.0, 3.0);
+        assert!(vec.is_finite());
+        assert!(!vec.is_nan());
+
+        let vec: Vec3<f32> = Vec3::new(1.0, f32::NAN, 3.0);
+        assert!(!vec.is_finite());
+        assert!(vec.is_nan());
+
+        let point: Point3<f64> = Point3::new(f64::NEG_INFINITY, 0.0, 0.0);
+        assert!(!point.is_finite());
+        assert!(!point.is_nan());
+        assert!(Point3::new(0.0, 0.0, f64::NAN).is_nan());
+    }
 
-pub type Point3d = Point3<f64>;
-pub type Point3f = Point3<f32>;
+    #[test]
+    fn bitwise_equality() {
+        let a: Vec3<f64> = Vec3::new(0.0, 1.0, 2.0);
+        let b: Vec3<f64> = Vec3::new(-0.0, 1.0, 2.0);
+        assert!(a == b);
+        assert!(!a.bit_eq(&b));
+        assert!(a.bit_eq(&a));
 
-#[cfg(test)]
-mod tests {
-    use super::{Point3, Tuple, Vec3, Vector};
+        let nan: Vec3<f32> = Vec3::new(f32::NAN, 0.0, 0.0);
+        assert!(nan != nan);
+        assert!(nan.bit_eq(&nan));
+    }
 
-    #[macro_export]
-    macro_rules! assert_approx_eq {
-        ($a:expr, $b:expr) => {{
-            let eps = 1.0e-6;
-            let (a, b) = (&$a, &$b);
-            assert!(
-                (*a - *b).abs() < eps,
-                "assertion failed: `(left !== right)` \
-                 (left: `{:?}`, right: `{:?}`, expect diff: `{:?}`, real diff: `{:?}`)",
-                *a,
-                *b,
-                eps,
-                (*a - *b).abs()
-            );
-        }};
-        ($a:expr, $b:expr, $eps:expr) => {{
-            let (a, b) = (&$a, &$b);
-            let eps = $eps;
-            assert!(
-                (*a - *b).abs() < eps,
-                "assertion failed: `(left !== right)` \
-                 (left: `{:?}`, right: `{:?}`, expect diff: `{:?}`, real diff: `{:?}`)",
-                *a,
-                *b,
-                eps,
-                (*a - *b).abs()
-            );
-        }};
+    #[test]
+    fn integer_vectors_as_hash_keys() {
+        use std::collections::HashSet;
+
+        let voxels: HashSet<Vec3<i32>> = [
+            Vec3::new(0, 0, 0),
+            Vec3::new(1, -2, 3),
+            Vec3::new(-4, 5, 6),
+            Vec3::new(1, -2, 3),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(voxels.len(), 3);
+        assert!(voxels.contains(&Vec3::new(1, -2, 3)));
+        assert!(voxels.contains(&Vec3::new(-4, 5, 6)));
+        assert!(!voxels.contains(&Vec3::new(3, -2, 1)));
+
+        let points: HashSet<Point3<u8>> = [Point3::new(1, 2, 3)].into_iter().collect();
+        assert!(points.contains(&Point3::new(1, 2, 3)));
+        assert!(!points.contains(&Point3::new(0, 2, 3)));
     }
 
     #[test]
-    fn create_and_modify_vector() {
+    fn lexicographic_ordering() {
+        let mut points = vec![
+            Point3::new(1, 2, 3),
+            Point3::new(0, 9, 9),
+            Point3::new(1, 2, -3),
+            Point3::new(1, -5, 0),
+        ];
+        points.sort();
+        assert_eq!(
+            points,
+            [
+                Point3::new(0, 9, 9),
+                Point3::new(1, -5, 0),
+                Point3::new(1, 2, -3),
+                Point3::new(1, 2, 3),
+            ]
+        );
+
+        let a = Vec3::new(1, 5, 0);
+        let b = Vec3::new(2, 0, 0);
+        assert!(a < b);
+        assert_eq!(a.max_components(&b), Vec3::new(2, 5, 0));
+        assert_eq!(a.min_components(&b), Vec3::new(1, 0, 0));
+        assert_eq!(
+            a.clamp_components(&Vec3::new(0, 1, 1), &Vec3::new(3, 3, 3)),
+            Vec3::new(1, 3, 1)
+        );
+    }
+
+    #[test]
+    fn integer_vectors_are_eq() {
+        fn assert_eq_impl<E: Eq>(_: &E) {}
+        assert_eq_impl(&Vec3::new(1, 2, 3));
+        assert_eq_impl(&Point3::new(1u8, 2, 3));
+    }
+
+    #[test]
+    fn normalize_both_float_types() {
+        let mut vec: Vec3<f32> = Vec3::new(0.0, 3.0, 4.0);
+        vec.normalize();
+        assert_eq!(vec, Vec3::new(0.0, 0.6, 0.8));
+
+        let mut vec: Vec3<f64> = Vec3::new(0.0, 3.0, 4.0);
+        vec.normalize();
+        assert!(vec.approx_eq(&Vec3::new(0.0, 0.6, 0.8), 1e-12));
+
+        let mut point: Point3<f32> = Point3::new(-4.0, 0.0, 3.0);
+        point.normalize();
+        assert_eq!(point, Point3::new(-0.8, 0.0, 0.6));
+
+        let mut zeros: Vec3<f32> = Vec3::zeros();
+        zeros.normalize();
+        assert_eq!(zeros, Vec3::zeros());
+    }
+
+    #[test]
+    fn zero_and_one() {
+        assert_eq!(f32::zero(), 0.0);
+        assert_eq!(f64::one(), 1.0);
+        assert_eq!(i64::zero(), 0);
+        assert_eq!(u8::one(), 1);
+    }
+
+    #[test]
+    fn normalized_copy() {
+        let vec: Vec3<f64> = Vec3::new(0.0, 3.0, 4.0);
+        let unit = vec.normalized();
+        assert_eq!(vec, Vec3::new(0.0, 3.0, 4.0));
+        assert!(unit.approx_eq(&Vec3::new(0.0, 0.6, 0.8), 1e-12));
+
+        let point: Point3<f64> = Point3::new(0.0, -3.0, 4.0);
+        let unit = point.normalized();
+        assert_eq!(point, Point3::new(0.0, -3.0, 4.0));
+        assert!(unit.approx_eq(&Point3::new(0.0, -0.6, 0.8), 1e-12));
+
         let zeros: Vec3<f64> = Vec3::zeros();
-        assert_eq!(zeros.x, 0.0);
-        assert_eq!(zeros[0], 0.0);
-        assert_eq!(zeros.y, 0.0);
-        assert_eq!(zeros[1], 0.0);
-        assert_eq!(zeros.z, 0.0);
-        assert_eq!(zeros[2], 0.0);
+        assert_eq!(zeros.normalized(), Vec3::zeros());
+        let origin: Point3<f64> = Point3::zeros();
+        assert_eq!(origin.normalized(), Point3::zeros());
+    }
 
-        let vec: Vec3<f64> = Vec3::new(1.1, 2.2, 3.3);
-        assert_eq!(vec.x, 1.1);
-        assert_eq!(vec.y, 2.2);
-        assert_eq!(vec.z, 3.3);
+    #[test]
+    fn checked_normalize() {
+        let mut vec: Vec3<f64> = Vec3::new(0.0, 3.0, 4.0);
+        assert!(vec.try_normalize());
+        assert!(vec.approx_eq(&Vec3::new(0.0, 0.6, 0.8), 1e-12));
 
-        let mut vec: Vec3<f64> = Vec3::zeros();
-        vec[0] = 1.1;
-        vec[1] = 2.2;
-        vec[2] = 3.3;
-        assert_eq!(vec[0], 1.1);
-        assert_eq!(vec[1], 2.2);
-        assert_eq!(vec[2], 3.3);
+        let mut zeros: Vec3<f32> = Vec3::zeros();
+        assert!(!zeros.try_normalize());
+        assert_eq!(zeros, Vec3::zeros());
+
+        let up = Vec3::new(0.0, 1.0, 0.0);
+        let vec: Vec3<f64> = Vec3::new(2.0, 0.0, 0.0);
+        assert_eq!(vec.normalized_or(up), Vec3::new(1.0, 0.0, 0.0));
+        assert_eq!(Vec3::zeros().normalized_or(up), up);
+        assert_eq!(Vec3::new(1e-13, 0.0, 0.0).normalized_or(up), up);
     }
 
     #[test]
-    fn create_point() {
-        let zeros: Point3<f64> = Point3::zeros();
-        assert_eq!(zeros.x, 0.0);
-        assert_eq!(zeros.y, 0.0);
-        assert_eq!(zeros.z, 0.0);
+    fn clamp_vector_magnitude() {
+        let short: Vec3<f64> = Vec3::new(1.0, 2.0, 2.0);
+        assert_eq!(short.clamp_magnitude(5.0), short);
+        assert_eq!(short.clamp_magnitude(3.0), short);
 
-        let vec: Point3<f64> = Point3::new(1.1, 2.2, 3.3);
-        assert_eq!(vec.x, 1.1);
-        assert_eq!(vec.y, 2.2);
-        assert_eq!(vec.z, 3.3);
+        let long: Vec3<f64> = Vec3::new(0.0, 30.0, 40.0);
+        let clamped = long.clamp_magnitude(5.0);
+        assert!(clamped.approx_eq(&Vec3::new(0.0, 3.0, 4.0), 1e-12));
+        assert_approx_eq!(clamped.magnitude(), 5.0, 1e-12);
+
+        let zeros: Vec3<f32> = Vec3::zeros();
+        assert_eq!(zeros.clamp_magnitude(1.0), zeros);
+        assert_eq!(zeros.clamp_magnitude(0.0), zeros);
     }
 
     #[test]
-    fn generic_sqrt() {
-        let vec: Vec3<f64> = Vec3::new(4.0, 49.0, 64.0);
-        assert_eq!(vec.x.sqrt(), 2.0);
-        assert_eq!(vec.y.sqrt(), 7.0);
-        assert_eq!(vec.z.sqrt(), 8.0);
+    fn set_vector_magnitude() {
+        let vec: Vec3<f64> = Vec3::new(1.0, -2.0, 0.5);
+        let resized = vec.with_magnitude(7.5);
+        assert_approx_eq!(resized.magnitude(), 7.5, 1e-12);
+        assert!(resized.normalized().approx_eq(&vec.normalized(), 1e-12));
 
-        let vec: Vec3<f32> = Vec3::new(4.0, 49.0, 64.0);
-        assert_eq!(vec.x.sqrt(), 2.0);
-        assert_eq!(vec.y.sqrt(), 7.0);
-        assert_eq!(vec.z.sqrt(), 8.0);
+        let shrunk: Vec3<f32> = Vec3::new(0.0, 30.0, 40.0).with_magnitude(5.0);
+        assert_eq!(shrunk, Vec3::new(0.0, 3.0, 4.0));
+
+        let zeros: Vec3<f64> = Vec3::zeros();
+        assert_eq!(zeros.with_magnitude(3.0), zeros);
     }
 
     #[test]
-    fn dot_product() {
-        let lhs: Vec3<f64> = Vec3::new(3.1, 5.0, -2.0);
-        let rhs: Vec3<f64> = Vec3::new(11.27, -9.0, 0.0);
-        let dot = Vec3::dot(&lhs, &rhs);
+    fn scalar_multiplication() {
+        let vec: Vec3<f64> = Vec3::new(1.0, 2.0, 3.0) * 2.0;
+        assert_eq!(vec.x, 2.0);
+        assert_eq!(vec.y, 4.0);
+        assert_eq!(vec.z, 6.0);
 
-        assert_approx_eq!(dot, -10.063, 1e-12);
+        let vec: Vec3<f32> = Vec3::new(1.0, -2.0, 0.5) * 4.0;
+        assert_eq!(vec.x, 4.0);
+        assert_eq!(vec.y, -8.0);
+        assert_eq!(vec.z, 2.0);
     }
 
     #[test]
-    fn cross_product() {
-        let lhs: Vec3<f64> = Vec3::new(3.1, 5.0, -2.0);
-        let rhs: Vec3<f64> = Vec3::new(11.27, -9.0, 0.0);
-        let vec = Vec3::cross(&lhs, &rhs);
-        assert_eq!(vec.x, -18.0);
-        assert_eq!(vec.y, -22.54);
-        assert_eq!(vec.z, -84.25);
+    fn scalar_multiplication_in_place() {
+        let mut vec: Vec3<f64> = Vec3::new(1.0, 2.0, 3.0);
+        vec *= 2.0;
+        assert_eq!(vec, Vec3::new(2.0, 4.0, 6.0));
 
-        let vec = lhs * rhs;
-        assert_eq!(vec.x, -18.0);
-        assert_eq!(vec.y, -22.54);
-        assert_eq!(vec.z, -84.25);
+        let mut point: Point3<f32> = Point3::new(1.0, -2.0, 0.5);
+        point *= 4.0;
+        assert_eq!(point, Point3::new(4.0, -8.0, 2.0));
+
+        let mut vec: Vec3<i32> = Vec3::new(1, -2, 3);
+        vec *= -3;
+        assert_eq!(vec, Vec3::new(-3, 6, -9));
     }
 
     #[test]
-    fn vec_magnitude() {
-        let vec: Vec3<f64> = Vec3::new(2.0, 0.0, 0.0);
-        assert_eq!(vec.magnitude(), 2.0);
+    fn scalar_division() {
+        let vec: Vec3<f64> = Vec3::new(1.0, 2.0, 3.0) / 3.0;
+        assert_eq!(vec, Vec3::new(1.0 / 3.0, 2.0 / 3.0, 1.0));
+
+        let mut vec: Vec3<f64> = Vec3::new(1.0, 2.0, 3.0);
+        vec /= 3.0;
+        assert_eq!(vec, Vec3::new(1.0 / 3.0, 2.0 / 3.0, 1.0));
+
+        let vec: Vec3<f32> = Vec3::new(4.0, -8.0, 2.0) / 4.0;
+        assert_eq!(vec, Vec3::new(1.0, -2.0, 0.5));
+
+        let mut vec: Vec3<f32> = Vec3::new(1.0, 2.0, 3.0);
+        vec /= 3.0;
+        assert_eq!(vec, Vec3::new(1.0 / 3.0, 2.0 / 3.0, 1.0));
     }
 
     #[test]
-    fn basic_vector_ops() {
-        let lhs: Vec3<f64> = Vec3::new(1.0, 2.0, 3.0);
-        let rhs: Vec3<f64> = Vec3::new(4.0, 5.0, 6.0);
-        let sum = lhs + rhs;
-        let diff = lhs - rhs;
+    fn negation() {
+        let vec: Vec3<f64> = -Vec3::new(1.0, -2.0, 3.0);
+        assert!(vec == Vec3::new(-1.0, 2.0, -3.0));
 
-        assert_eq!(sum.x, 5.0);
-        assert_eq!(sum.y, 7.0);
-        assert_eq!(sum.z, 9.0);
+        let point: Point3<f64> = -Point3::new(1.0, -2.0, 3.0);
+        assert!(point == Point3::new(-1.0, 2.0, -3.0));
+    }
 
-        assert_eq!(diff.x, -3.0);
-        assert_eq!(diff.y, -3.0);
-        assert_eq!(diff.z, -3.0);
+    #[test]
+    fn assign_ops() {
+        let a: Vec3<f64> = Vec3::new(1.0, 2.0, 3.0);
+        let b: Vec3<f64> = Vec3::new(0.5, -1.0, 4.0);
+        let c: Vec3<f64> = Vec3::new(-2.0, 0.25, 1.0);
+
+        let mut sum = Vec3::zeros();
+        sum += a;
+        sum += b;
+        sum += c;
+        assert!(sum == a + b + c);
+
+        sum -= c;
+        assert!(sum == a + b);
+
+        let mut point: Point3<f64> = Point3::new(1.0, 2.0, 3.0);
+        point += Point3::new(1.0, 1.0, 1.0);
+        assert!(point == Point3::new(2.0, 3.0, 4.0));
+        point -= Point3::new(2.0, 2.0, 2.0);
+        assert!(point == Point3::new(0.0, 1.0, 2.0));
     }
 
     #[test]
-    fn normalize_vector_and_point() {
+    fn linear_interpolation() {
+        let a: Vec3<f64> = Vec3::new(1.0, 2.0, 3.0);
+        let b: Vec3<f64> = Vec3::new(3.0, -2.0, 4.0);
+        assert!(Vec3::lerp(&a, &b, 0.0) == a);
+        assert!(Vec3::lerp(&a, &b, 0.5) == Vec3::new(2.0, 0.0, 3.5));
+        assert!(Vec3::lerp(&a, &b, 1.0) == b);
+        assert!(Vec3::lerp(&a, &b, 2.0) == Vec3::new(5.0, -6.0, 5.0));
+
+        let a: Point3<f64> = Point3::new(1.0, 2.0, 3.0);
+        let b: Point3<f64> = Point3::new(3.0, -2.0, 4.0);
+        assert!(Point3::lerp(&a, &b, 0.0) == a);
+        assert!(Point3::lerp(&a, &b, 0.5) == Point3::new(2.0, 0.0, 3.5));
+        assert!(Point3::lerp(&a, &b, 1.0) == b);
+    }
+
+    #[test]
+    fn distance_between() {
+        let a: Point3<f64> = Point3::new(1.0, 2.0, 3.0);
+        let b: Point3<f64> = Point3::new(3.0, 5.0, 9.0);
+        assert_eq!(a.distance(&b), 7.0);
+        assert_eq!(b.distance(&a), 7.0);
+        assert_eq!(a.distance_squared(&b), 49.0);
+
+        let a: Vec3<f64> = Vec3::new(0.5, -1.0, 2.0);
+        let b: Vec3<f64> = Vec3::new(-1.5, 3.0, 1.0);
+        let distance = a.distance(&b);
+        assert_approx_eq!(distance, 21.0f64.sqrt(), 1e-12);
+        assert_approx_eq!(a.distance_squared(&b), distance * distance, 1e-12);
+    }
+
+    #[test]
+    fn vec_magnitude_squared() {
+        let vec: Vec3<f64> = Vec3::new(1.5, -2.0, 3.0);
+        let magnitude = vec.magnitude();
+        assert_approx_eq!(vec.magnitude_squared(), magnitude * magnitude, 1e-12);
+
+        let vec: Vec3<i32> = Vec3::new(2, -3, 6);
+        assert_eq!(vec.magnitude_squared(), 49);
+    }
+
+    #[test]
+    fn checked_access() {
         let mut vec: Vec3<f64> = Vec3::new(1.0, 2.0, 3.0);
-        vec.normalize();
-        assert_approx_eq!(vec.x, 0.2672612419124244, 1e-12);
-        assert_approx_eq!(vec.y, 0.5345224838248488, 1e-12);
-        assert_approx_eq!(vec.z, 0.8017837257372732, 1e-12);
+        assert_eq!(vec.get(2), Some(&3.0));
+        assert_eq!(vec.get(3), None);
+        *vec.get_mut(2).unwrap() = 4.0;
+        assert_eq!(vec.z, 4.0);
+        assert!(vec.get_mut(3).is_none());
 
         let mut point: Point3<f64> = Point3::new(1.0, 2.0, 3.0);
-        point.normalize();
-        assert_approx_eq!(point.x, 0.2672612419124244, 1e-12);
-        assert_approx_eq!(point.y, 0.5345224838248488, 1e-12);
-        assert_approx_eq!(point.z, 0.8017837257372732, 1e-12);
+        assert_eq!(point.get(2), Some(&3.0));
+        assert_eq!(point.get(3), None);
+        *point.get_mut(0).unwrap() = 5.0;
+        assert_eq!(point.x, 5.0);
+        assert!(point.get_mut(3).is_none());
+    }
+
+    #[test]
+    fn debug_format() {
+        let vec: Vec3<f64> = Vec3::new(1.0, -2.5, 3.0);
+        assert_eq!(format!("{:?}", vec), "Vec3 { x: 1.0, y: -2.5, z: 3.0 }");
+
+        let point: Point3<i32> = Point3::new(1, 2, 3);
+        assert_eq!(format!("{:?}", point), "Point3 { x: 1, y: 2, z: 3 }");
+    }
+
+    #[test]
+    fn display_format() {
+        let vec: Vec3<f64> = Vec3::new(1.0, 2.0, 3.0);
+        assert_eq!(format!("{:.2}", vec), "(1.00, 2.00, 3.00)");
+        assert_eq!(format!("{}", vec), "(1, 2, 3)");
+
+        let point: Point3<f32> = Point3::new(0.125, -2.5, 3.0);
+        assert_eq!(format!("{:.1}", point), "(0.1, -2.5, 3.0)");
+        assert_eq!(format!("{}", point), "(0.125, -2.5, 3)");
+    }
+
+    #[test]
+    fn component_wise_ops() {
+        let a: Vec3<f64> = Vec3::new(1.0, -2.0, 3.0);
+        let b: Vec3<f64> = Vec3::new(4.0, 0.5, -2.0);
+        assert_eq!(a.component_mul(&b), Vec3::new(4.0, -1.0, -6.0));
+        assert_eq!(a.component_div(&b), Vec3::new(0.25, -4.0, -1.5));
+        assert_eq!(a.component_mul(&b).component_div(&b), a);
+    }
+
+    #[test]
+    fn sum_and_product() {
+        let vs: Vec<Vec3<f64>> = vec![
+            Vec3::new(1.0, -2.0, 3.0),
+            Vec3::new(4.0, 0.5, -2.0),
+            Vec3::new(-0.5, 1.0, 2.0),
+        ];
+        let total: Vec3<f64> = vs.iter().copied().sum();
+        assert_eq!(total, vs[0] + vs[1] + vs[2]);
+        assert_eq!(total, Vec3::new(4.5, -0.5, 3.0));
+
+        let product: Vec3<f64> = vs.iter().copied().product();
+        assert_eq!(product, vs[0].component_mul(&vs[1]).component_mul(&vs[2]));
+        assert_eq!(product, Vec3::new(-2.0, -1.0, -12.0));
+
+        let empty: Vec<Vec3<f64>> = Vec::new();
+        assert_eq!(empty.iter().copied().sum::<Vec3<f64>>(), Vec3::zeros());
+        assert_eq!(empty.into_iter().product::<Vec3<f64>>(), Vec3::one());
+    }
+
+    #[test]
+    fn component_min_max_clamp() {
+        let cloud: [Vec3<f64>; 4] = [
+            Vec3::new(1.0, -2.0, 3.0),
+            Vec3::new(-4.0, 5.0, 0.5),
+            Vec3::new(2.0, 0.0, -1.0),
+            Vec3::new(0.0, 1.0, 7.0),
+        ];
+        let lo = cloud.iter().fold(cloud[0], |acc, v| acc.min_components(v));
+        let hi = cloud.iter().fold(cloud[0], |acc, v| acc.max_components(v));
+        assert_eq!(lo, Vec3::new(-4.0, -2.0, -1.0));
+        assert_eq!(hi, Vec3::new(2.0, 5.0, 7.0));
+
+        let v: Vec3<f64> = Vec3::new(10.0, -10.0, 0.0);
+        assert_eq!(v.clamp_components(&lo, &hi), Vec3::new(2.0, -2.0, 0.0));
+
+        let nan: Vec3<f64> = Vec3::new(f64::NAN, 1.0, 1.0);
+        assert!(nan.min_components(&lo).x.is_nan());
+        assert_eq!(lo.min_components(&nan).x, -4.0);
+    }
+
+    #[test]
+    fn absolute_value() {
+        let vec: Vec3<f64> = Vec3::new(-1.0, 2.0, -3.0);
+        assert_eq!(vec.abs(), Vec3::new(1.0, 2.0, 3.0));
+
+        let point: Point3<f32> = Point3::new(0.5, -0.0, -7.25);
+        assert_eq!(point.abs(), Point3::new(0.5, 0.0, 7.25));
+    }
+
+    #[test]
+    fn reflect_and_refract() {
+        let incident: Vec3<f64> = Vec3::new(1.0, -1.0, 0.0);
+        let normal: Vec3<f64> = Vec3::new(0.0, 1.0, 0.0);
+        assert_eq!(Vec3::reflect(&incident, &normal), Vec3::new(1.0, 1.0, 0.0));
+
+        let straight: Vec3<f64> = Vec3::new(0.0, -1.0, 0.0);
+        let refracted = Vec3::refract(&straight, &normal, 1.5).unwrap();
+        assert_approx_eq!(refracted.x, 0.0, 1e-12);
+        assert_approx_eq!(refracted.y, -1.0, 1e-12);
+
+        // 30 degrees from inside glass (n = 1.5) into air: sin(t) = 0.75.
+        let angle = std::f64::consts::PI / 6.0;
+        let incident: Vec3<f64> = Vec3::new(angle.sin(), -angle.cos(), 0.0);
+        let refracted = Vec3::refract(&incident, &normal, 1.5).unwrap();
+        assert_approx_eq!(refracted.magnitude(), 1.0, 1e-12);
+        assert_approx_eq!(refracted.x, 0.75, 1e-12);
+        assert_approx_eq!(refracted.y, -(1.0f64 - 0.75 * 0.75).sqrt(), 1e-12);
+
+        // 45 degrees exceeds the critical angle of glass.
+        let incident: Vec3<f64> = Vec3::new(1.0, -1.0, 0.0) * (1.0 / 2.0f64.sqrt());
+        assert!(Vec3::refract(&incident, &normal, 1.5).is_none());
+    }
+
+    #[test]
+    fn projection_and_rejection() {
+        let v: Vec3<f64> = Vec3::new(3.0, 4.0, 5.0);
+        let onto: Vec3<f64> = Vec3::new(2.0, 0.0, 0.0);
+        assert_eq!(v.project_onto(&onto), Vec3::new(3.0, 0.0, 0.0));
+        assert_eq!(v.reject_from(&onto), Vec3::new(0.0, 4.0, 5.0));
+
+        let onto: Vec3<f64> = Vec3::new(1.0, -2.0, 0.5);
+        let projection = v.project_onto(&onto);
+        let rejection = v.reject_from(&onto);
+        let sum = projection + rejection;
+        assert_approx_eq!(sum.x, v.x, 1e-12);
+        assert_approx_eq!(sum.y, v.y, 1e-12);
+        assert_approx_eq!(sum.z, v.z, 1e-12);
+        assert_approx_eq!(Vec3::dot(&rejection, &onto), 0.0, 1e-12);
+
+        assert_eq!(v.project_onto(&Vec3::zeros()), Vec3::zeros());
+        assert_eq!(v.reject_from(&Vec3::zeros()), v);
+    }
+
+    #[test]
+    fn angle_between_vectors() {
+        use std::f64::consts::PI;
+
+        let x: Vec3<f64> = Vec3::new(2.0, 0.0, 0.0);
+        let y: Vec3<f64> = Vec3::new(0.0, 3.0, 0.0);
+        assert_approx_eq!(Vec3::angle_between(&x, &y), PI / 2.0, 1e-12);
+
+        let v: Vec3<f64> = Vec3::new(0.1, 0.2, 0.3);
+        assert_eq!(Vec3::angle_between(&v, &v), 0.0);
+        assert_approx_eq!(Vec3::angle_between(&v, &-v), PI, 1e-12);
+        assert_approx_eq!(
+            Vec3::angle_between(&x, &Vec3::new(1.0, 1.0, 0.0)),
+            PI / 4.0,
+            1e-12
+        );
+    }
+
+    #[test]
+    fn array_conversions() {
+        let vec: Vec3<f32> = [1.0, 2.0, 3.0].into();
+        assert_eq!(vec, Vec3::new(1.0, 2.0, 3.0));
+        let array: [f32; 3] = vec.into();
+        assert_eq!(array, [1.0, 2.0, 3.0]);
+
+        let point = Point3::from([4, 5, 6]);
+        assert_eq!(point, Point3::new(4, 5, 6));
+        let array: [i32; 3] = point.into();
+        assert_eq!(array, [4, 5, 6]);
+    }
+
+    #[test]
+    fn point_vector_conversions() {
+        let vec: Vec3<f64> = Vec3::new(1.0, 2.0, 3.0);
+        let point = Point3::from_vec(&vec);
+        assert_eq!(point, Point3::new(1.0, 2.0, 3.0));
+        assert_eq!(Vec3::from_point(&point), vec);
+
+        let a: Point3<f64> = Point3::new(1.0, 1.0, 1.0);
+        let b: Point3<f64> = Point3::new(4.0, 5.0, 1.0);
+        let d = b.difference(&a);
+        assert_eq!(d, Vec3::new(3.0, 4.0, 0.0));
+        assert_eq!(d.magnitude(), 5.0);
+    }
+
+    #[test]
+    fn point_difference_is_vector() {
+        let a: Point3<f64> = Point3::new(1.0, 2.0, 3.0);
+        let b: Point3<f64> = Point3::new(4.0, 6.0, 3.0);
+
+        let mut d: Vec3<f64> = b - a;
+        assert_eq!(d, Vec3::new(3.0, 4.0, 0.0));
+        assert_eq!(Vec3::dot(&d, &Vec3::new(1.0, 0.0, 0.0)), 3.0);
+        d.normalize();
+        assert_approx_eq!(d.x, 0.6, 1e-12);
+        assert_approx_eq!(d.y, 0.8, 1e-12);
+    }
+
+    #[test]
+    fn point_vector_arithmetic() {
+        let origin: Point3<f64> = Point3::new(1.0, 2.0, 3.0);
+        let direction: Vec3<f64> = Vec3::new(0.0, 0.0, -1.0);
+
+        assert_eq!(origin + direction * 2.5, Point3::new(1.0, 2.0, 0.5));
+        assert_eq!(origin - direction, Point3::new(1.0, 2.0, 4.0));
+
+        let target = origin + Vec3::new(3.0, -1.0, 2.0);
+        assert_eq!(target - origin, Vec3::new(3.0, -1.0, 2.0));
+    }
+
+    #[test]
+    fn approximate_equality() {
+        let a: Vec3<f64> = Vec3::new(1.0, 2.0, 3.0);
+        assert!(a.approx_eq(&Vec3::new(1.0 + 1e-10, 2.0 - 1e-10, 3.0), 1e-9));
+        assert!(!a.approx_eq(&Vec3::new(1.0, 2.0, 3.0 + 1.1e-9), 1e-9));
+
+        let p: Point3<f32> = Point3::new(1.0, 2.0, 3.0);
+        assert!(p.approx_eq(&Point3::new(1.0, 2.0005, 3.0), 1e-3));
+        assert!(!p.approx_eq(&Point3::new(1.0011, 2.0, 3.0), 1e-3));
+    }
+
+    #[test]
+    fn component_iteration() {
+        let vec: Vec3<f64> = Vec3::new(1.0, 2.0, 3.5);
+        let sum: f64 = vec.iter().copied().sum();
+        assert_eq!(sum, 6.5);
+        assert_eq!(vec.into_iter().collect::<Vec<_>>(), vec![1.0, 2.0, 3.5]);
+
+        let point: Point3<i32> = Point3::new(4, -5, 6);
+        assert_eq!(point.iter().sum::<i32>(), 5);
+        let mut total = 0;
+        for c in point {
+            total += c;
+        }
+        assert_eq!(total, 5);
+    }
+
+    #[test]
+    fn map_components() {
+        let vec: Vec3<f64> = Vec3::new(1.0, -2.0, 3.5);
+        assert_eq!(vec.map(|c| c * 2.0), Vec3::new(2.0, -4.0, 7.0));
+
+        let converted: Vec3<f32> = vec.map(|c| c as f32);
+        assert_eq!(converted, Vec3::new(1.0f32, -2.0, 3.5));
+
+        let point: Point3<f64> = Point3::new(1.7, -1.2, 2.5);
+        assert_eq!(point.map(f64::floor), Point3::new(1.0, -2.0, 2.0));
+        assert_eq!(point.map(|c| c as i32), Point3::new(1, -1, 2));
+    }
+
+    #[test]
+    fn zip_components() {
+        let a: Vec3<f64> = Vec3::new(1.0, -2.0, 3.0);
+        let b: Vec3<f64> = Vec3::new(0.5, 4.0, -3.0);
+        assert_eq!(a.zip_with(&b, f64::max), a.max_components(&b));
+        assert_eq!(a.zip_with(&b, |x, y| x * y), a.component_mul(&b));
+        assert_eq!(a.zip_with(&b, |x, y| x + y), a + b);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let vec: Vec3<f64> = Vec3::new(1.0, -2.5, 3.0);
+        let json = serde_json::to_string(&vec).unwrap();
+        assert_eq!(json, r#"{"x":1.0,"y":-2.5,"z":3.0}"#);
+        assert_eq!(serde_json::from_str::<Vec3<f64>>(&json).unwrap(), vec);
+
+        let point: Point3<i32> = Point3::new(1, 2, 3);
+        let json = serde_json::to_string(&point).unwrap();
+        assert_eq!(json, r#"{"x":1,"y":2,"z":3}"#);
+        assert_eq!(serde_json::from_str::<Point3<i32>>(&json).unwrap(), point);
+    }
+
+    #[test]
+    fn integer_sqrt() {
+        assert_eq!(Sqrt::sqrt(&49i32), 7);
+        assert_eq!(Sqrt::sqrt(&0i64), 0);
+        assert_eq!(Sqrt::sqrt(&10u32), 3);
+        assert_eq!(Sqrt::sqrt(&99u64), 9);
+        assert_eq!(Sqrt::sqrt(&(1u64 << 62)), 1 << 31);
+        assert_eq!(Sqrt::sqrt(&u64::MAX), u64::from(u32::MAX));
+        assert_eq!(Sqrt::sqrt(&i64::MAX), 3_037_000_499);
+        assert_eq!(Sqrt::sqrt(&i32::MAX), 46_340);
+        for n in 0u32..2000 {
+            let root = Sqrt::sqrt(&n);
+            assert!(root * root <= n && (root + 1) * (root + 1) > n);
+        }
+
+        let vec: Vec3<i32> = Vec3::new(2, 3, 6);
+        assert_eq!(vec.magnitude(), 7);
+        let vec: Vec3<i32> = Vec3::new(1, 1, 1);
+        assert_eq!(vec.magnitude(), 1);
+    }
+
+    fn animate<M: Mix<f64>>(a: &M, b: &M) -> [M; 3] {
+        [a.mix(b, 0.0), a.mix(b, 0.25), a.mix(b, 1.0)]
+    }
+
+    #[test]
+    fn generic_mix() {
+        let [start, quarter, end] = animate(&2.0, &6.0);
+        assert_eq!(start, 2.0);
+        assert_eq!(quarter, 3.0);
+        assert_eq!(end, 6.0);
+
+        let a: Vec3<f64> = Vec3::new(0.0, 4.0, -8.0);
+        let b: Vec3<f64> = Vec3::new(4.0, 0.0, 8.0);
+        let [start, quarter, end] = animate(&a, &b);
+        assert_eq!(start, a);
+        assert_eq!(quarter, Vec3::new(1.0, 3.0, -4.0));
+        assert_eq!(end, b);
+
+        let a: Point3<f64> = Point3::new(0.0, 4.0, -8.0);
+        let b: Point3<f64> = Point3::new(4.0, 0.0, 8.0);
+        let [start, quarter, end] = animate(&a, &b);
+        assert_eq!(start, a);
+        assert_eq!(quarter, Point3::new(1.0, 3.0, -4.0));
+        assert_eq!(end, b);
+
+        assert_eq!(1.0f32.mix(&3.0, 0.5), 2.0);
     }
 }
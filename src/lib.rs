@@ -1,5 +1,41 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+#[cfg(not(any(feature = "std", feature = "libm")))]
+compile_error!("mafs requires either the `std` or the `libm` feature");
+
 mod basic_types;
 pub use basic_types::*;
 
+mod scalar;
+pub use scalar::*;
+
+mod vec2;
+pub use vec2::*;
+
+mod vec4;
+pub use vec4::*;
+
+mod swizzle;
+
 mod matrix;
 pub use matrix::*;
+
+mod quaternion;
+pub use quaternion::*;
+
+mod ray;
+pub use ray::*;
+
+mod aabb;
+pub use aabb::*;
+
+mod plane;
+pub use plane::*;
+
+mod sphere;
+pub use sphere::*;
+
+#[cfg(feature = "simd")]
+mod simd;
+#[cfg(feature = "simd")]
+pub use simd::*;
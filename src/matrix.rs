@@ -1,48 +1,311 @@
-use std::ops::{Add, Index, IndexMut, Mul, Div};
+use core::fmt;
+use core::ops::{Add, AddAssign, Div, Index, IndexMut, Mul, MulAssign, Neg, Sub, SubAssign};
 
-use crate::{Point3, Tuple, Vec3};
+use crate::{
+    ApproxEq, Atan2, CheckedArith, Epsilon, Finite, FromF64, One, Plane, Point3, Quaternion, Sqrt,
+    Trig, Tuple, Vec3, Vec4, Vector, Zero,
+};
 
-pub struct Matrix4<T> {
-    pub m: [[T; 4]; 4],
+/// Row-major matrix with `R` rows and `C` columns. `Matrix4` and `Matrix3`
+/// are the square aliases used for transforms; other shapes support the
+/// element-wise operations and dimension-checked products.
+///
+/// With the `serde` feature, serialized as a nested array of rows.
+#[derive(Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        transparent,
+        bound(
+            serialize = "[[T; C]; R]: serde::Serialize",
+            deserialize = "[[T; C]; R]: serde::Deserialize<'de>"
+        )
+    )
+)]
+pub struct Matrix<T, const R: usize, const C: usize> {
+    pub m: [[T; C]; R],
 }
 
-impl<T> Default for Matrix4<T>
+pub type Matrix4<T> = Matrix<T, 4, 4>;
+pub type Matrix3<T> = Matrix<T, 3, 3>;
+
+impl<T, const R: usize, const C: usize> Default for Matrix<T, R, C>
 where
     T: Default,
 {
     fn default() -> Self {
-        Self {
-            m: Default::default(),
+        Self::zeros()
+    }
+}
+
+impl<T: fmt::Debug, const R: usize, const C: usize> fmt::Debug for Matrix<T, R, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if R == C {
+            writeln!(f, "Matrix{} [", R)?;
+        } else {
+            writeln!(f, "Matrix{}x{} [", R, C)?;
+        }
+        for row in &self.m {
+            writeln!(f, "    {:?},", row)?;
+        }
+        write!(f, "]")
+    }
+}
+
+/// Prints the rows on separate lines with right-aligned columns. The
+/// precision flag is applied to every element.
+impl<T: fmt::Display, const R: usize, const C: usize> fmt::Display for Matrix<T, R, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        struct Width(usize);
+
+        impl fmt::Write for Width {
+            fn write_str(&mut self, s: &str) -> fmt::Result {
+                self.0 += s.chars().count();
+                Ok(())
+            }
+        }
+
+        let precision = f.precision();
+        let mut width = 0;
+        for value in self.m.iter().flatten() {
+            let mut cell = Width(0);
+            match precision {
+                Some(p) => fmt::write(&mut cell, format_args!("{:.*}", p, value))?,
+                None => fmt::write(&mut cell, format_args!("{}", value))?,
+            }
+            width = width.max(cell.0);
         }
+
+        for (i, row) in self.m.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "[")?;
+            for (j, value) in row.iter().enumerate() {
+                if j > 0 {
+                    write!(f, ", ")?;
+                }
+                match precision {
+                    Some(p) => write!(f, "{:>w$.p$}", value, w = width, p = p)?,
+                    None => write!(f, "{:>w$}", value, w = width)?,
+                }
+            }
+            write!(f, "]")?;
+        }
+        Ok(())
     }
 }
 
-impl<T> Index<usize> for Matrix4<T> {
-    type Output = [T; 4];
+impl<T: Copy + ApproxEq<T>, const R: usize, const C: usize> ApproxEq<T> for Matrix<T, R, C> {
+    fn approx_eq(&self, other: &Self, epsilon: T) -> bool {
+        self.m
+            .iter()
+            .flatten()
+            .zip(other.m.iter().flatten())
+            .all(|(a, b)| a.approx_eq(b, epsilon))
+    }
+}
+
+impl<T, const R: usize, const C: usize> Index<usize> for Matrix<T, R, C> {
+    type Output = [T; C];
 
     fn index(&self, index: usize) -> &Self::Output {
         &self.m[index]
     }
 }
 
-impl<T> IndexMut<usize> for Matrix4<T> {
+impl<T, const R: usize, const C: usize> IndexMut<usize> for Matrix<T, R, C> {
     fn index_mut(&mut self, index: usize) -> &mut Self::Output {
         &mut self.m[index]
     }
 }
 
-impl<T> Mul for Matrix4<T>
+/// `Matrix * Matrix` is the matrix product, defined when the column count of
+/// the left operand matches the row count of the right one. See
+/// `Matrix * T` for scaling.
+impl<T, const R: usize, const K: usize, const C: usize> Mul<Matrix<T, K, C>> for Matrix<T, R, K>
 where
     T: Copy + Default + Add<Output = T> + Mul<Output = T>,
 {
+    type Output = Matrix<T, R, C>;
+
+    fn mul(self, rhs: Matrix<T, K, C>) -> Self::Output {
+        Matrix {
+            m: core::array::from_fn(|i| {
+                // Summing from the first product rather than from zero keeps
+                // the rounding (and the sign of zero) identical to
+                // `multiply_simd`.
+                core::array::from_fn(|j| {
+                    (0..K)
+                        .map(|k| self[i][k] * rhs[k][j])
+                        .reduce(|sum, term| sum + term)
+                        .unwrap_or_default()
+                })
+            }),
+        }
+    }
+}
+
+/// `Matrix * T` scales every element. Not to be confused with
+/// `Matrix * Matrix`, which is the matrix product.
+impl<T: Copy + Mul<Output = T>, const R: usize, const C: usize> Mul<T> for Matrix<T, R, C> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        let mut result = self;
+        for row in result.m.iter_mut() {
+            for value in row.iter_mut() {
+                *value = *value * rhs;
+            }
+        }
+        result
+    }
+}
+
+/// Element-wise sum.
+impl<T: Copy + Add<Output = T>, const R: usize, const C: usize> Add for Matrix<T, R, C> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let mut result = self;
+        for i in 0..R {
+            for j in 0..C {
+                result[i][j] = self[i][j] + rhs[i][j];
+            }
+        }
+        result
+    }
+}
+
+/// Element-wise difference.
+impl<T: Copy + Sub<Output = T>, const R: usize, const C: usize> Sub for Matrix<T, R, C> {
     type Output = Self;
 
-    fn mul(self, rhs: Self) -> Self::Output {
-        multiply(&self, &rhs)
+    fn sub(self, rhs: Self) -> Self::Output {
+        let mut result = self;
+        for i in 0..R {
+            for j in 0..C {
+                result[i][j] = self[i][j] - rhs[i][j];
+            }
+        }
+        result
+    }
+}
+
+impl<T: Copy + AddAssign, const R: usize, const C: usize> AddAssign for Matrix<T, R, C> {
+    fn add_assign(&mut self, rhs: Self) {
+        for i in 0..R {
+            for j in 0..C {
+                self[i][j] += rhs[i][j];
+            }
+        }
+    }
+}
+
+impl<T: Copy + SubAssign, const R: usize, const C: usize> SubAssign for Matrix<T, R, C> {
+    fn sub_assign(&mut self, rhs: Self) {
+        for i in 0..R {
+            for j in 0..C {
+                self[i][j] -= rhs[i][j];
+            }
+        }
+    }
+}
+
+impl<T, const R: usize, const C: usize> Matrix<T, R, C> {
+    pub fn zeros() -> Self
+    where
+        T: Default,
+    {
+        Self {
+            m: core::array::from_fn(|_| core::array::from_fn(|_| T::default())),
+        }
+    }
+
+    pub fn get(&self, row: usize) -> Option<&[T; C]> {
+        self.m.get(row)
+    }
+
+    pub fn get_element(&self, row: usize, col: usize) -> Option<&T> {
+        self.m.get(row).and_then(|r| r.get(col))
+    }
+
+    pub fn transposed(&self) -> Matrix<T, C, R>
+    where
+        T: Copy,
+    {
+        Matrix {
+            m: core::array::from_fn(|i| core::array::from_fn(|j| self[j][i])),
+        }
+    }
+
+    /// Matrix product that returns `None` instead of wrapping or panicking
+    /// when an integer element overflows.
+    pub fn checked_mul<const K: usize>(lhs: &Self, rhs: &Matrix<T, C, K>) -> Option<Matrix<T, R, K>>
+    where
+        T: Copy + Default + CheckedArith,
+    {
+        let mut result = Matrix::zeros();
+        for i in 0..R {
+            for j in 0..K {
+                let mut sum = T::default();
+                for k in 0..C {
+                    sum = sum.checked_add(&lhs[i][k].checked_mul(&rhs[k][j])?)?;
+                }
+                result[i][j] = sum;
+            }
+        }
+        Some(result)
+    }
+
+    /// `true` when no element is infinite or NaN.
+    pub fn is_finite(&self) -> bool
+    where
+        T: Finite,
+    {
+        self.m.iter().flatten().all(|x| x.is_finite())
+    }
+
+    /// `true` when any element is NaN.
+    pub fn is_nan(&self) -> bool
+    where
+        T: Finite,
+    {
+        self.m.iter().flatten().any(|x| x.is_nan())
+    }
+}
+
+impl<T, const N: usize> Matrix<T, N, N> {
+    pub fn identity() -> Self
+    where
+        T: Default + One,
+    {
+        let mut result = Self::zeros();
+        for (i, row) in result.m.iter_mut().enumerate() {
+            row[i] = T::one();
+        }
+        result
+    }
+
+    pub fn transpose(&mut self)
+    where
+        T: Copy,
+    {
+        *self = self.transposed();
+    }
+
+    /// Sum of the diagonal elements.
+    pub fn trace(&self) -> T
+    where
+        T: Copy + Add<Output = T>,
+    {
+        (1..N).fold(self[0][0], |sum, i| sum + self[i][i])
     }
 }
 
 impl<T> Matrix4<T> {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         a: T,
         b: T,
@@ -69,184 +332,1140 @@ impl<T> Matrix4<T> {
         }
     }
 
-    pub fn zeros() -> Self
-    where
-        T: Default,
-    {
+    /// Matrix whose row `i` is `rows[i]`.
+    pub fn from_rows(rows: [Vec4<T>; 4]) -> Self {
         Self {
-            ..Default::default()
+            m: rows.map(|r| [r.x, r.y, r.z, r.w]),
         }
     }
 
-    pub fn transpose(&mut self)
+    /// Matrix whose column `j` is `cols[j]`, i.e. the transpose of
+    /// `from_rows(cols)`.
+    pub fn from_cols(cols: [Vec4<T>; 4]) -> Self
     where
         T: Copy,
     {
-        let tmp = Self::new(
-            self[0][0], self[1][0], self[2][0], self[3][0], self[0][1], self[1][1], self[2][1],
-            self[3][1], self[0][2], self[1][2], self[2][2], self[3][2], self[0][3], self[1][3],
-            self[2][3], self[3][3],
-        );
+        let mut result = Self::from_rows(cols);
+        result.transpose();
+        result
+    }
+
+    /// Flattens the matrix row by row: element `[i][j]` lands at index
+    /// `4 * i + j`. Because this crate uses row vectors, that is the same
+    /// memory layout that column-major APIs such as OpenGL expect for the
+    /// equivalent column-vector matrix, so it can be uploaded untransposed.
+    pub fn to_array(&self) -> [T; 16]
+    where
+        T: Copy,
+    {
+        let m = &self.m;
+        [
+            m[0][0], m[0][1], m[0][2], m[0][3], m[1][0], m[1][1], m[1][2], m[1][3], m[2][0],
+            m[2][1], m[2][2], m[2][3], m[3][0], m[3][1], m[3][2], m[3][3],
+        ]
+    }
 
-        *self = tmp;
+    /// Flattens the matrix column by column: element `[i][j]` lands at index
+    /// `4 * j + i`.
+    pub fn to_array_col_major(&self) -> [T; 16]
+    where
+        T: Copy,
+    {
+        self.transposed().to_array()
     }
 
-    pub fn transposed(&self) -> Self
+    /// Inverse of `to_array`: reads the elements row by row.
+    pub fn from_array(a: [T; 16]) -> Self
     where
         T: Copy,
     {
         Self::new(
-            self[0][0], self[1][0], self[2][0], self[3][0], self[0][1], self[1][1], self[2][1],
-            self[3][1], self[0][2], self[1][2], self[2][2], self[3][2], self[0][3], self[1][3],
-            self[2][3], self[3][3],
+            a[0], a[1], a[2], a[3], a[4], a[5], a[6], a[7], a[8], a[9], a[10], a[11], a[12], a[13],
+            a[14], a[15],
         )
     }
 
-    pub fn inverse(&mut self) {
-        todo!("Work in progress");
+    /// Inverse of `to_array_col_major`: reads the elements column by column.
+    pub fn from_array_col_major(a: [T; 16]) -> Self
+    where
+        T: Copy,
+    {
+        Self::from_array(a).transposed()
     }
-}
 
-pub fn multiply<T>(lhs: &Matrix4<T>, rhs: &Matrix4<T>) -> Matrix4<T>
-where
-    T: Default + Copy + Mul<Output = T> + Add<Output = T>,
-{
-    let mut result = Matrix4::zeros();
-
-    let b0 = rhs[0];
-    let b1 = rhs[1];
-    let b2 = rhs[2];
-    let b3 = rhs[3];
-
-    let a = lhs[0];
-    result[0][0] = a[0] * b0[0] + a[1] * b1[0] + a[2] * b2[0] + a[3] * b3[0];
-    result[0][1] = a[0] * b0[1] + a[1] * b1[1] + a[2] * b2[1] + a[3] * b3[1];
-    result[0][2] = a[0] * b0[2] + a[1] * b1[2] + a[2] * b2[2] + a[3] * b3[2];
-    result[0][3] = a[0] * b0[3] + a[1] * b1[3] + a[2] * b2[3] + a[3] * b3[3];
-
-    let a = lhs[1];
-    result[1][0] = a[0] * b0[0] + a[1] * b1[0] + a[2] * b2[0] + a[3] * b3[0];
-    result[1][1] = a[0] * b0[1] + a[1] * b1[1] + a[2] * b2[1] + a[3] * b3[1];
-    result[1][2] = a[0] * b0[2] + a[1] * b1[2] + a[2] * b2[2] + a[3] * b3[2];
-    result[1][3] = a[0] * b0[3] + a[1] * b1[3] + a[2] * b2[3] + a[3] * b3[3];
-
-    let a = lhs[2];
-    result[2][0] = a[0] * b0[0] + a[1] * b1[0] + a[2] * b2[0] + a[3] * b3[0];
-    result[2][1] = a[0] * b0[1] + a[1] * b1[1] + a[2] * b2[1] + a[3] * b3[1];
-    result[2][2] = a[0] * b0[2] + a[1] * b1[2] + a[2] * b2[2] + a[3] * b3[2];
-    result[2][3] = a[0] * b0[3] + a[1] * b1[3] + a[2] * b2[3] + a[3] * b3[3];
-
-    let a = lhs[3];
-    result[3][0] = a[0] * b0[0] + a[1] * b1[0] + a[2] * b2[0] + a[3] * b3[0];
-    result[3][1] = a[0] * b0[1] + a[1] * b1[1] + a[2] * b2[1] + a[3] * b3[1];
-    result[3][2] = a[0] * b0[2] + a[1] * b1[2] + a[2] * b2[2] + a[3] * b3[2];
-    result[3][3] = a[0] * b0[3] + a[1] * b1[3] + a[2] * b2[3] + a[3] * b3[3];
-    result
-}
+    /// Row `i` as a vector. Panics when `i` is out of range.
+    pub fn row(&self, i: usize) -> Vec4<T>
+    where
+        T: Copy,
+    {
+        let r = self[i];
+        Vec4::new(r[0], r[1], r[2], r[3])
+    }
 
-pub fn mul_point_matrix<T>(p: &Point3<T>, m: &Matrix4<T>) -> Point3<T> where T: Copy + Mul<Output = T> + Add<Output = T> + Div<Output = T> {
-    let x = p[0] * m[0][0] + p[1] * m[1][0] + p[2] * m[2][0] + m[3][0];
-    let y = p[0] * m[0][1] + p[1] * m[1][1] + p[2] * m[2][1] + m[3][1];
-    let z = p[0] * m[0][2] + p[1] * m[1][2] + p[2] * m[2][2] + m[3][2];
-    let w = p[0] * m[0][3] + p[1] * m[1][3] + p[2] * m[2][3] + m[3][3];
+    /// Column `j` as a vector. Panics when `j` is out of range.
+    pub fn column(&self, j: usize) -> Vec4<T>
+    where
+        T: Copy,
+    {
+        Vec4::new(self[0][j], self[1][j], self[2][j], self[3][j])
+    }
 
-    Point3::new(x/w, y/w, z/w)
-}
+    pub fn set_row(&mut self, i: usize, row: &Vec4<T>)
+    where
+        T: Copy,
+    {
+        self[i] = [row.x, row.y, row.z, row.w];
+    }
 
-pub fn mul_vec_matrix<T>(v: &Vec3<T>, m: &Matrix4<T>) -> Vec3<T> where T: Copy + Mul<Output = T> + Add<Output = T> + Div<Output = T> {
-    let x = v[0] * m[0][0] + v[1] * m[1][0] + v[2] * m[2][0];
-    let y = v[0] * m[0][1] + v[1] * m[1][1] + v[2] * m[2][1];
-    let z = v[0] * m[0][2] + v[1] * m[1][2] + v[2] * m[2][2];
+    pub fn set_column(&mut self, j: usize, column: &Vec4<T>)
+    where
+        T: Copy,
+    {
+        for i in 0..4 {
+            self[i][j] = column[i];
+        }
+    }
 
-    Vec3::new(x, y, z)
-}
+    /// Translation lives in row 3, matching the row-vector convention of
+    /// `mul_point_matrix`.
+    pub fn translation(tx: T, ty: T, tz: T) -> Self
+    where
+        T: Default + One,
+    {
+        let mut result = Self::identity();
+        result[3][0] = tx;
+        result[3][1] = ty;
+        result[3][2] = tz;
+        result
+    }
 
-pub type Mat4d = Matrix4<f64>;
-pub type Mat4f = Matrix4<f32>;
+    pub fn scaling(sx: T, sy: T, sz: T) -> Self
+    where
+        T: Default + One,
+    {
+        let mut result = Self::identity();
+        result[0][0] = sx;
+        result[1][1] = sy;
+        result[2][2] = sz;
+        result
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::{multiply, Mat4d, Matrix4};
+    /// Shear where each factor names the coordinate that moves and the one it
+    /// moves in proportion to: `x' = x + xy * y + xz * z`,
+    /// `y' = y + yx * x + yz * z` and `z' = z + zx * x + zy * y`.
+    pub fn shear(xy: T, xz: T, yx: T, yz: T, zx: T, zy: T) -> Self
+    where
+        T: Default + One,
+    {
+        let mut result = Self::identity();
+        result[1][0] = xy;
+        result[2][0] = xz;
+        result[0][1] = yx;
+        result[2][1] = yz;
+        result[0][2] = zx;
+        result[1][2] = zy;
+        result
+    }
 
-    #[test]
-    fn create_matrix() {
-        let m1 = Mat4d {
-            ..Default::default()
-        };
-        for i in 0..4 {
-            for j in 0..4 {
-                assert_eq!(m1[i][j], 0f64);
-            }
-        }
+    /// Rotation of `angle` radians about the x axis.
+    pub fn rotation_x(angle: T) -> Self
+    where
+        T: Copy + Default + One + Neg<Output = T> + Trig,
+    {
+        let (s, c) = (angle.sin(), angle.cos());
+        let mut result = Self::identity();
+        result[1][1] = c;
+        result[1][2] = s;
+        result[2][1] = -s;
+        result[2][2] = c;
+        result
+    }
 
-        let m2 = Mat4d::new(
-            1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0, 11.0, 12.0, 13.0, 14.0, 15.0, 16.0,
-        );
-        assert_eq!(m2[0][0], 1.0);
-        assert_eq!(m2[0][1], 2.0);
-        assert_eq!(m2[0][2], 3.0);
-        assert_eq!(m2[0][3], 4.0);
-        assert_eq!(m2[1][0], 5.0);
-        assert_eq!(m2[1][1], 6.0);
-        assert_eq!(m2[1][2], 7.0);
-        assert_eq!(m2[1][3], 8.0);
-        assert_eq!(m2[2][0], 9.0);
-        assert_eq!(m2[2][1], 10.0);
-        assert_eq!(m2[2][2], 11.0);
-        assert_eq!(m2[2][3], 12.0);
-        assert_eq!(m2[3][0], 13.0);
-        assert_eq!(m2[3][1], 14.0);
-        assert_eq!(m2[3][2], 15.0);
-        assert_eq!(m2[3][3], 16.0);
+    /// Rotation of `angle` radians about the y axis.
+    pub fn rotation_y(angle: T) -> Self
+    where
+        T: Copy + Default + One + Neg<Output = T> + Trig,
+    {
+        let (s, c) = (angle.sin(), angle.cos());
+        let mut result = Self::identity();
+        result[0][0] = c;
+        result[0][2] = -s;
+        result[2][0] = s;
+        result[2][2] = c;
+        result
     }
 
-    #[test]
-    fn multiply_matrieces() {
-        let m1 = Matrix4::new(5, 7, 9, 10, 2, 3, 3, 8, 8, 10, 2, 3, 3, 3, 4, 8);
-        let m2 = Matrix4::new(3, 10, 12, 18, 12, 1, 4, 9, 9, 10, 12, 2, 3, 12, 4, 10);
-        let result = multiply(&m1, &m2);
-        assert_eq!(result[0][0], 210);
-        assert_eq!(result[0][1], 267);
-        assert_eq!(result[0][2], 236);
-        assert_eq!(result[0][3], 271);
-        assert_eq!(result[1][0], 93);
-        assert_eq!(result[1][1], 149);
-        assert_eq!(result[1][2], 104);
-        assert_eq!(result[1][3], 149);
-        assert_eq!(result[2][0], 171);
-        assert_eq!(result[2][1], 146);
-        assert_eq!(result[2][2], 172);
-        assert_eq!(result[2][3], 268);
-        assert_eq!(result[3][0], 105);
-        assert_eq!(result[3][1], 169);
-        assert_eq!(result[3][2], 128);
-        assert_eq!(result[3][3], 169);
+    /// Rotation of `angle` radians about the z axis.
+    pub fn rotation_z(angle: T) -> Self
+    where
+        T: Copy + Default + One + Neg<Output = T> + Trig,
+    {
+        let (s, c) = (angle.sin(), angle.cos());
+        let mut result = Self::identity();
+        result[0][0] = c;
+        result[0][1] = s;
+        result[1][0] = -s;
+        result[1][1] = c;
+        result
+    }
 
-        let result = m1 * m2;
-        assert_eq!(result[0][0], 210);
-        assert_eq!(result[0][1], 267);
-        assert_eq!(result[0][2], 236);
-        assert_eq!(result[0][3], 271);
-        assert_eq!(result[1][0], 93);
-        assert_eq!(result[1][1], 149);
-        assert_eq!(result[1][2], 104);
-        assert_eq!(result[1][3], 149);
-        assert_eq!(result[2][0], 171);
-        assert_eq!(result[2][1], 146);
-        assert_eq!(result[2][2], 172);
-        assert_eq!(result[2][3], 268);
-        assert_eq!(result[3][0], 105);
-        assert_eq!(result[3][1], 169);
-        assert_eq!(result[3][2], 128);
-        assert_eq!(result[3][3], 169);
+    /// Rotation of `angle` radians about an arbitrary axis using Rodrigues'
+    /// formula. The axis is normalized internally and must not be zero.
+    pub fn rotation_axis(axis: &Vec3<T>, angle: T) -> Self
+    where
+        T: Copy
+            + Default
+            + One
+            + Add<Output = T>
+            + Sub<Output = T>
+            + Mul<Output = T>
+            + Div<Output = T>
+            + Sqrt
+            + Trig,
+    {
+        let len = axis.magnitude();
+        let (x, y, z) = (axis.x / len, axis.y / len, axis.z / len);
+        let (s, c) = (angle.sin(), angle.cos());
+        let t = T::one() - c;
+
+        let mut result = Self::identity();
+        result[0][0] = c + x * x * t;
+        result[0][1] = x * y * t + z * s;
+        result[0][2] = x * z * t - y * s;
+        result[1][0] = x * y * t - z * s;
+        result[1][1] = c + y * y * t;
+        result[1][2] = y * z * t + x * s;
+        result[2][0] = x * z * t + y * s;
+        result[2][1] = y * z * t - x * s;
+        result[2][2] = c + z * z * t;
+        result
     }
 
-    #[test]
-    fn transpose_test() {
-        let mut m = Matrix4::new(5, 7, 9, 10, 2, 3, 3, 8, 8, 10, 2, 3, 3, 3, 4, 8);
-        m.transpose();
-        assert_eq!(m[0][0], 5);
-        assert_eq!(m[0][1], 2);
+    /// Rotation by `x` radians about the x axis, then `y` about y, then `z`
+    /// about z, i.e. `rotation_x(x) * rotation_y(y) * rotation_z(z)`. The
+    /// inverse of `to_euler_xyz`.
+    pub fn from_euler_xyz(x: T, y: T, z: T) -> Self
+    where
+        T: Copy + Default + One + Add<Output = T> + Mul<Output = T> + Neg<Output = T> + Trig,
+    {
+        Self::rotation_x(x).then_rotate_y(y).then_rotate_z(z)
+    }
+
+    /// Mirror transform across `plane`, whose normal must be unit length as
+    /// the `Plane` constructors leave it. A point `p` maps to
+    /// `p - 2 * plane.signed_distance(p) * plane.normal`, which in the
+    /// row-vector convention is the Householder block `I - 2 n^T n` with
+    /// `-2 d n` in the translation row.
+    pub fn reflection(plane: &Plane<T>) -> Self
+    where
+        T: Copy + Default + One + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+    {
+        let n = plane.normal;
+        let two = T::one() + T::one();
+
+        let mut result = Self::identity();
+        for i in 0..3 {
+            for j in 0..3 {
+                result[i][j] = result[i][j] - two * n[i] * n[j];
+            }
+            result[3][i] = T::default() - two * plane.d * n[i];
+        }
+        result
+    }
+
+    /// Right-handed perspective projection looking down -z. Depth is mapped
+    /// to the OpenGL clip range: the near plane lands at z = -1 and the far
+    /// plane at z = 1 after the w-divide in `mul_point_matrix`.
+    pub fn perspective(fov_y: T, aspect: T, near: T, far: T) -> Self
+    where
+        T: Copy
+            + Default
+            + One
+            + Add<Output = T>
+            + Sub<Output = T>
+            + Mul<Output = T>
+            + Div<Output = T>
+            + Neg<Output = T>
+            + Trig,
+    {
+        let two = T::one() + T::one();
+        let f = T::one() / (fov_y / two).tan();
+
+        let mut result = Self::zeros();
+        result[0][0] = f / aspect;
+        result[1][1] = f;
+        result[2][2] = (far + near) / (near - far);
+        result[2][3] = -T::one();
+        result[3][2] = two * far * near / (near - far);
+        result
+    }
+
+    /// Right-handed orthographic projection looking down -z, consistent with
+    /// `perspective`: the view box maps to the [-1, 1] cube with the near
+    /// plane (z = -near) at -1 and the far plane (z = -far) at 1.
+    pub fn orthographic(left: T, right: T, bottom: T, top: T, near: T, far: T) -> Self
+    where
+        T: Copy
+            + Default
+            + One
+            + Add<Output = T>
+            + Sub<Output = T>
+            + Mul<Output = T>
+            + Div<Output = T>
+            + Neg<Output = T>,
+    {
+        let two = T::one() + T::one();
+
+        let mut result = Self::identity();
+        result[0][0] = two / (right - left);
+        result[1][1] = two / (top - bottom);
+        result[2][2] = -two / (far - near);
+        result[3][0] = -(right + left) / (right - left);
+        result[3][1] = -(top + bottom) / (top - bottom);
+        result[3][2] = -(far + near) / (far - near);
+        result
+    }
+
+    /// Right-handed view matrix with the camera at `eye` looking towards
+    /// `target`, so that `target` ends up on the negative z axis. When `up`
+    /// is parallel to the viewing direction, the world axis least aligned
+    /// with the viewing direction is used as `up` instead.
+    pub fn look_at(eye: &Point3<T>, target: &Point3<T>, up: &Vec3<T>) -> Self
+    where
+        T: Copy
+            + Default
+            + One
+            + Add<Output = T>
+            + Sub<Output = T>
+            + Mul<Output = T>
+            + Div<Output = T>
+            + MulAssign
+            + Neg<Output = T>
+            + Zero
+            + Sqrt
+            + PartialOrd,
+    {
+        let zero = T::default();
+        let mut f = *target - *eye;
+        f.normalize();
+
+        let mut s = f.cross(up);
+        if Vec3::dot(&s, &s) == zero {
+            let (xx, yy, zz) = (f.x * f.x, f.y * f.y, f.z * f.z);
+            let axis = if xx <= yy && xx <= zz {
+                Vec3::new(T::one(), zero, zero)
+            } else if yy <= zz {
+                Vec3::new(zero, T::one(), zero)
+            } else {
+                Vec3::new(zero, zero, T::one())
+            };
+            s = f.cross(&axis);
+        }
+        s.normalize();
+        let u = s.cross(&f);
+
+        let e = Vec3::new(eye.x, eye.y, eye.z);
+        Self::new(
+            s.x,
+            u.x,
+            -f.x,
+            zero,
+            s.y,
+            u.y,
+            -f.y,
+            zero,
+            s.z,
+            u.z,
+            -f.z,
+            zero,
+            -Vec3::dot(&s, &e),
+            -Vec3::dot(&u, &e),
+            Vec3::dot(&f, &e),
+            T::one(),
+        )
+    }
+
+    /// Computes the determinant by Laplace expansion over 2x2 minors of the
+    /// top and bottom row pairs.
+    pub fn determinant(&self) -> T
+    where
+        T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+    {
+        let m = &self.m;
+
+        let s0 = m[0][0] * m[1][1] - m[0][1] * m[1][0];
+        let s1 = m[0][0] * m[1][2] - m[0][2] * m[1][0];
+        let s2 = m[0][0] * m[1][3] - m[0][3] * m[1][0];
+        let s3 = m[0][1] * m[1][2] - m[0][2] * m[1][1];
+        let s4 = m[0][1] * m[1][3] - m[0][3] * m[1][1];
+        let s5 = m[0][2] * m[1][3] - m[0][3] * m[1][2];
+
+        let c0 = m[2][0] * m[3][1] - m[2][1] * m[3][0];
+        let c1 = m[2][0] * m[3][2] - m[2][2] * m[3][0];
+        let c2 = m[2][0] * m[3][3] - m[2][3] * m[3][0];
+        let c3 = m[2][1] * m[3][2] - m[2][2] * m[3][1];
+        let c4 = m[2][1] * m[3][3] - m[2][3] * m[3][1];
+        let c5 = m[2][2] * m[3][3] - m[2][3] * m[3][2];
+
+        s0 * c5 - s1 * c4 + s2 * c3 + s3 * c2 - s4 * c1 + s5 * c0
+    }
+
+    /// Inverts the matrix in place. Returns `false` and leaves the matrix
+    /// untouched when it is singular.
+    pub fn inverse(&mut self) -> bool
+    where
+        T: Copy
+            + Default
+            + Add<Output = T>
+            + Sub<Output = T>
+            + Mul<Output = T>
+            + Div<Output = T>
+            + One
+            + Epsilon
+            + PartialOrd,
+    {
+        match self.inversed() {
+            Some(inv) => {
+                *self = inv;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Gauss-Jordan elimination with partial pivoting. Returns `None` when a
+    /// pivot is within rounding noise of zero, i.e. no larger than four
+    /// machine epsilons times the largest absolute entry. The tolerance
+    /// scales with the matrix, so uniformly tiny matrices still invert.
+    pub fn inversed(&self) -> Option<Self>
+    where
+        T: Copy
+            + Default
+            + Add<Output = T>
+            + Sub<Output = T>
+            + Mul<Output = T>
+            + Div<Output = T>
+            + One
+            + Epsilon
+            + PartialOrd,
+    {
+        let zero = T::default();
+        let one = T::one();
+        let abs = |x: T| if x < zero { zero - x } else { x };
+
+        // No determinant pre-check: it can overflow, and an exact `== 0` test
+        // rejects tiny well-conditioned matrices whose determinant underflows.
+        // The pivot tolerance below already catches singular input.
+        let largest = self.m.iter().flatten().fold(zero, |acc, &x| {
+            let x = abs(x);
+            if x > acc {
+                x
+            } else {
+                acc
+            }
+        });
+        let four = one + one + one + one;
+        let tolerance = four * T::epsilon() * largest;
+
+        let mut a = self.m;
+        let mut inv = [[zero; 4]; 4];
+        for (i, row) in inv.iter_mut().enumerate() {
+            row[i] = one;
+        }
+
+        for col in 0..4 {
+            let mut pivot = col;
+            for row in col + 1..4 {
+                if abs(a[row][col]) > abs(a[pivot][col]) {
+                    pivot = row;
+                }
+            }
+            if abs(a[pivot][col]) <= tolerance {
+                return None;
+            }
+            a.swap(col, pivot);
+            inv.swap(col, pivot);
+
+            let p = a[col][col];
+            for j in 0..4 {
+                a[col][j] = a[col][j] / p;
+                inv[col][j] = inv[col][j] / p;
+            }
+
+            for row in 0..4 {
+                if row == col {
+                    continue;
+                }
+                let factor = a[row][col];
+                for j in 0..4 {
+                    a[row][j] = a[row][j] - factor * a[col][j];
+                    inv[row][j] = inv[row][j] - factor * inv[col][j];
+                }
+            }
+        }
+
+        Some(Self { m: inv })
+    }
+
+    /// Inverse of an affine transform, assuming the last column is
+    /// `[0, 0, 0, 1]`. Only the upper-left 3x3 block is inverted, via its
+    /// adjugate; the inverse translation is the original one pushed through
+    /// that inverse and negated. Much cheaper than `inversed`, but wrong for
+    /// projections. Returns `None` when the 3x3 block is singular.
+    pub fn inverse_affine(&self) -> Option<Self>
+    where
+        T: Copy
+            + Default
+            + One
+            + Add<Output = T>
+            + Sub<Output = T>
+            + Mul<Output = T>
+            + Div<Output = T>
+            + Neg<Output = T>
+            + PartialEq,
+    {
+        let inv = self.upper_left_3x3().inversed()?;
+        let t = mul_vec_matrix3(&Vec3::new(self[3][0], self[3][1], self[3][2]), &inv);
+
+        let mut result = Self::identity();
+        for i in 0..3 {
+            for j in 0..3 {
+                result[i][j] = inv[i][j];
+            }
+        }
+        result[3][0] = -t.x;
+        result[3][1] = -t.y;
+        result[3][2] = -t.z;
+        Some(result)
+    }
+
+    /// Inverse of a pure rotation, which is the transpose of its upper-left
+    /// 3x3 block. Only valid when that block is orthonormal; translation,
+    /// scale and projection terms are discarded. With the `debug-checks`
+    /// feature, debug builds assert orthonormality to within 1e-4, which
+    /// needs the extra bounds of the checked variant.
+    #[cfg(not(all(feature = "debug-checks", debug_assertions)))]
+    pub fn inverse_rotation(&self) -> Self
+    where
+        T: Copy + Default + One,
+    {
+        self.transposed_rotation()
+    }
+
+    /// Inverse of a pure rotation, which is the transpose of its upper-left
+    /// 3x3 block. This variant asserts that the block is orthonormal to
+    /// within 1e-4 before transposing it.
+    #[cfg(all(feature = "debug-checks", debug_assertions))]
+    pub fn inverse_rotation(&self) -> Self
+    where
+        T: Copy + Default + One + Add<Output = T> + Mul<Output = T> + ApproxEq<T> + FromF64,
+    {
+        self.assert_orthonormal_rotation();
+        self.transposed_rotation()
+    }
+
+    #[cfg(all(feature = "debug-checks", debug_assertions))]
+    fn assert_orthonormal_rotation(&self)
+    where
+        T: Copy + Default + One + Add<Output = T> + Mul<Output = T> + ApproxEq<T> + FromF64,
+    {
+        let r = self.upper_left_3x3();
+        assert!(
+            (r * r.transposed()).approx_eq(&Matrix3::identity(), T::from_f64(1.0e-4)),
+            "inverse_rotation called on a matrix that is not a pure rotation"
+        );
+    }
+
+    fn transposed_rotation(&self) -> Self
+    where
+        T: Copy + Default + One,
+    {
+        let mut result = Self::identity();
+        for i in 0..3 {
+            for j in 0..3 {
+                result[i][j] = self[j][i];
+            }
+        }
+        result
+    }
+
+    /// World-space corners of the frustum of a view-projection matrix, found
+    /// by pushing the corners of the [-1, 1] clip cube through the inverse.
+    /// Corner `i` takes x from bit 0, y from bit 1 and z from bit 2 of `i`
+    /// (clear for -1, set for 1), so the first four lie on the near plane.
+    /// Returns `None` when the matrix cannot be inverted. A singular
+    /// view-projection (e.g. one that flattens depth) has no frustum, and
+    /// returning `Option` makes callers handle that case instead of
+    /// receiving meaningless corners.
+    pub fn frustum_corners(&self) -> Option<[Point3<T>; 8]>
+    where
+        T: Copy
+            + Default
+            + Add<Output = T>
+            + Sub<Output = T>
+            + Mul<Output = T>
+            + Div<Output = T>
+            + Neg<Output = T>
+            + One
+            + Epsilon
+            + PartialOrd,
+    {
+        let inv = self.inversed()?;
+        let one = T::one();
+        let sign = |bit: bool| if bit { one } else { -one };
+        Some(core::array::from_fn(|i| {
+            let ndc = Point3::new(sign(i & 1 != 0), sign(i & 2 != 0), sign(i & 4 != 0));
+            mul_point_matrix(&ndc, &inv)
+        }))
+    }
+
+    /// Transforms `p` as a point, applying translation and the perspective
+    /// divide. Method form of `mul_point_matrix`.
+    pub fn transform_point(&self, p: &Point3<T>) -> Point3<T>
+    where
+        T: Copy + Mul<Output = T> + Add<Output = T> + Div<Output = T>,
+    {
+        mul_point_matrix(p, self)
+    }
+
+    /// Transforms `v` as a direction using only the upper-left 3x3 block, so
+    /// translation is ignored. Method form of `mul_vec_matrix`.
+    pub fn transform_direction(&self, v: &Vec3<T>) -> Vec3<T>
+    where
+        T: Copy + Mul<Output = T> + Add<Output = T> + Div<Output = T>,
+    {
+        mul_vec_matrix(v, self)
+    }
+
+    /// Appends `next` so it is applied after `self`. In the row-vector
+    /// convention this is the product `self * next`.
+    pub fn then(self, next: &Self) -> Self
+    where
+        T: Copy + Default + Add<Output = T> + Mul<Output = T>,
+    {
+        multiply(&self, next)
+    }
+
+    /// Replaces `self` with `other * self`, i.e. prepends `other` so it is
+    /// applied before the current transform. Same result as
+    /// `*self = *other * *self`, but the product is built row by row in a
+    /// local scratch array instead of a whole-matrix temporary.
+    pub fn mul_assign_left(&mut self, other: &Self)
+    where
+        T: Copy + Add<Output = T> + Mul<Output = T>,
+    {
+        let b = &self.m;
+        let scratch = other.m.map(|a| {
+            [
+                a[0] * b[0][0] + a[1] * b[1][0] + a[2] * b[2][0] + a[3] * b[3][0],
+                a[0] * b[0][1] + a[1] * b[1][1] + a[2] * b[2][1] + a[3] * b[3][1],
+                a[0] * b[0][2] + a[1] * b[1][2] + a[2] * b[2][2] + a[3] * b[3][2],
+                a[0] * b[0][3] + a[1] * b[1][3] + a[2] * b[2][3] + a[3] * b[3][3],
+            ]
+        });
+        self.m = scratch;
+    }
+
+    pub fn then_translate(self, t: &Vec3<T>) -> Self
+    where
+        T: Copy + Default + One + Add<Output = T> + Mul<Output = T>,
+    {
+        self.then(&Self::translation(t.x, t.y, t.z))
+    }
+
+    pub fn then_scale(self, s: &Vec3<T>) -> Self
+    where
+        T: Copy + Default + One + Add<Output = T> + Mul<Output = T>,
+    {
+        self.then(&Self::scaling(s.x, s.y, s.z))
+    }
+
+    pub fn then_rotate_x(self, angle: T) -> Self
+    where
+        T: Copy + Default + One + Add<Output = T> + Mul<Output = T> + Neg<Output = T> + Trig,
+    {
+        self.then(&Self::rotation_x(angle))
+    }
+
+    pub fn then_rotate_y(self, angle: T) -> Self
+    where
+        T: Copy + Default + One + Add<Output = T> + Mul<Output = T> + Neg<Output = T> + Trig,
+    {
+        self.then(&Self::rotation_y(angle))
+    }
+
+    pub fn then_rotate_z(self, angle: T) -> Self
+    where
+        T: Copy + Default + One + Add<Output = T> + Mul<Output = T> + Neg<Output = T> + Trig,
+    {
+        self.then(&Self::rotation_z(angle))
+    }
+
+    pub fn then_rotate_axis(self, axis: &Vec3<T>, angle: T) -> Self
+    where
+        T: Copy
+            + Default
+            + One
+            + Add<Output = T>
+            + Sub<Output = T>
+            + Mul<Output = T>
+            + Div<Output = T>
+            + Sqrt
+            + Trig,
+    {
+        self.then(&Self::rotation_axis(axis, angle))
+    }
+}
+
+/// The product `lhs * rhs`, taking its operands by reference.
+pub fn multiply<T>(lhs: &Matrix4<T>, rhs: &Matrix4<T>) -> Matrix4<T>
+where
+    T: Default + Copy + Mul<Output = T> + Add<Output = T>,
+{
+    *lhs * *rhs
+}
+
+/// Transforms `p` as the row vector `[x, y, z, 1] * m`, then divides by the
+/// resulting `w`. Component `j` of the result is `sum_i p[i] * m[i][j]`, so
+/// translation is read from row 3 (`m[3][0..3]`) and `a * b` applies `a`
+/// first. Matrices written for the column-vector convention (`m * p`, with
+/// translation in column 3) must be transposed before use here.
+pub fn mul_point_matrix<T>(p: &Point3<T>, m: &Matrix4<T>) -> Point3<T> where T: Copy + Mul<Output = T> + Add<Output = T> + Div<Output = T> {
+    let x = p[0] * m[0][0] + p[1] * m[1][0] + p[2] * m[2][0] + m[3][0];
+    let y = p[0] * m[0][1] + p[1] * m[1][1] + p[2] * m[2][1] + m[3][1];
+    let z = p[0] * m[0][2] + p[1] * m[1][2] + p[2] * m[2][2] + m[3][2];
+    let w = p[0] * m[0][3] + p[1] * m[1][3] + p[2] * m[2][3] + m[3][3];
+
+    Point3::new(x/w, y/w, z/w)
+}
+
+/// Transforms `v` as the row vector `[x, y, z, 0] * m`, the direction
+/// counterpart of `mul_point_matrix`. Row 3 and column 3 are ignored.
+pub fn mul_vec_matrix<T>(v: &Vec3<T>, m: &Matrix4<T>) -> Vec3<T> where T: Copy + Mul<Output = T> + Add<Output = T> + Div<Output = T> {
+    let x = v[0] * m[0][0] + v[1] * m[1][0] + v[2] * m[2][0];
+    let y = v[0] * m[0][1] + v[1] * m[1][1] + v[2] * m[2][1];
+    let z = v[0] * m[0][2] + v[1] * m[1][2] + v[2] * m[2][2];
+
+    Vec3::new(x, y, z)
+}
+
+impl<T> Matrix3<T> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(a: T, b: T, c: T, d: T, e: T, f: T, g: T, h: T, i: T) -> Self
+    where
+        T: Copy,
+    {
+        Self {
+            m: [[a, b, c], [d, e, f], [g, h, i]],
+        }
+    }
+
+    pub fn determinant(&self) -> T
+    where
+        T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+    {
+        let m = &self.m;
+        m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+            - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+            + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+    }
+
+    /// Inverts the matrix in place. Returns `false` and leaves the matrix
+    /// untouched when it is singular.
+    pub fn inverse(&mut self) -> bool
+    where
+        T: Copy
+            + Default
+            + Add<Output = T>
+            + Sub<Output = T>
+            + Mul<Output = T>
+            + Div<Output = T>
+            + PartialEq,
+    {
+        match self.inversed() {
+            Some(inv) => {
+                *self = inv;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Inverse via the adjugate. Returns `None` when the determinant is zero.
+    pub fn inversed(&self) -> Option<Self>
+    where
+        T: Copy
+            + Default
+            + Add<Output = T>
+            + Sub<Output = T>
+            + Mul<Output = T>
+            + Div<Output = T>
+            + PartialEq,
+    {
+        let det = self.determinant();
+        if det == T::default() {
+            return None;
+        }
+
+        let m = &self.m;
+        Some(Self::new(
+            (m[1][1] * m[2][2] - m[1][2] * m[2][1]) / det,
+            (m[0][2] * m[2][1] - m[0][1] * m[2][2]) / det,
+            (m[0][1] * m[1][2] - m[0][2] * m[1][1]) / det,
+            (m[1][2] * m[2][0] - m[1][0] * m[2][2]) / det,
+            (m[0][0] * m[2][2] - m[0][2] * m[2][0]) / det,
+            (m[0][2] * m[1][0] - m[0][0] * m[1][2]) / det,
+            (m[1][0] * m[2][1] - m[1][1] * m[2][0]) / det,
+            (m[0][1] * m[2][0] - m[0][0] * m[2][1]) / det,
+            (m[0][0] * m[1][1] - m[0][1] * m[1][0]) / det,
+        ))
+    }
+}
+
+impl<T> Matrix4<T> {
+    /// Rotation and scale block of the matrix.
+    pub fn upper_left_3x3(&self) -> Matrix3<T>
+    where
+        T: Copy,
+    {
+        Matrix3::new(
+            self[0][0], self[0][1], self[0][2], self[1][0], self[1][1], self[1][2], self[2][0],
+            self[2][1], self[2][2],
+        )
+    }
+
+    /// Splits an affine transform into `(translation, rotation, scale)` such
+    /// that `scaling * rotation.to_matrix4() * translation` rebuilds it. In the
+    /// row-vector convention the scaled basis vectors are the first three
+    /// rows, so the scale is taken from their lengths. A mirroring transform
+    /// (negative determinant) is reported as a negative x scale. The scale
+    /// must be non-zero on every axis.
+    pub fn decompose(&self) -> (Vec3<T>, Quaternion<T>, Vec3<T>)
+    where
+        T: Copy
+            + Default
+            + One
+            + Add<Output = T>
+            + Sub<Output = T>
+            + Mul<Output = T>
+            + Div<Output = T>
+            + Neg<Output = T>
+            + Sqrt
+            + PartialOrd,
+    {
+        let translation = Vec3::new(self[3][0], self[3][1], self[3][2]);
+
+        let rows = [0, 1, 2].map(|i| Vec3::new(self[i][0], self[i][1], self[i][2]));
+        let mut scale = Vec3::new(
+            rows[0].magnitude(),
+            rows[1].magnitude(),
+            rows[2].magnitude(),
+        );
+        if self.upper_left_3x3().determinant() < T::default() {
+            scale.x = -scale.x;
+        }
+
+        let mut rotation = Self::identity();
+        for (i, row) in rows.iter().enumerate() {
+            for j in 0..3 {
+                rotation[i][j] = row[j] / scale[i];
+            }
+        }
+
+        (translation, Quaternion::from_matrix(&rotation), scale)
+    }
+
+    /// Builds the transform that scales, then rotates, then translates,
+    /// i.e. `scaling * rotation.to_matrix4() * translation`. The inverse of
+    /// `decompose`; `rotation` is expected to be normalized.
+    pub fn compose(translation: &Vec3<T>, rotation: &Quaternion<T>, scale: &Vec3<T>) -> Self
+    where
+        T: Copy + Default + One + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+    {
+        let mut result = rotation.to_matrix4();
+        for i in 0..3 {
+            for j in 0..3 {
+                result[i][j] = result[i][j] * scale[i];
+            }
+        }
+        result[3][0] = translation.x;
+        result[3][1] = translation.y;
+        result[3][2] = translation.z;
+        result
+    }
+
+    /// Angles `(x, y, z)` such that `from_euler_xyz(x, y, z)` reproduces the
+    /// upper-left 3x3 block, which must be a pure rotation. `y` is in
+    /// `[-pi/2, pi/2]`, `x` and `z` in `[-pi, pi]`.
+    ///
+    /// When `y` is within about 1e-6 of +-90 degrees (gimbal lock), x and z
+    /// rotate about the same axis and only their combination is defined. In
+    /// that case `z` is reported as zero and the whole remaining rotation is
+    /// folded into `x`.
+    pub fn to_euler_xyz(&self) -> (T, T, T)
+    where
+        T: Copy
+            + Default
+            + Add<Output = T>
+            + Mul<Output = T>
+            + Neg<Output = T>
+            + PartialOrd
+            + Sqrt
+            + Atan2
+            + FromF64,
+    {
+        let m = &self.m;
+        let cos_y = (m[0][0] * m[0][0] + m[0][1] * m[0][1]).sqrt();
+        let y = (-m[0][2]).atan2(&cos_y);
+
+        if cos_y < T::from_f64(1.0e-6) {
+            let x = (-m[2][1]).atan2(&m[1][1]);
+            (x, y, T::default())
+        } else {
+            let x = m[1][2].atan2(&m[2][2]);
+            let z = m[0][1].atan2(&m[0][0]);
+            (x, y, z)
+        }
+    }
+}
+
+pub fn mul_vec_matrix3<T>(v: &Vec3<T>, m: &Matrix3<T>) -> Vec3<T>
+where
+    T: Copy + Mul<Output = T> + Add<Output = T>,
+{
+    let x = v[0] * m[0][0] + v[1] * m[1][0] + v[2] * m[2][0];
+    let y = v[0] * m[0][1] + v[1] * m[1][1] + v[2] * m[2][1];
+    let z = v[0] * m[0][2] + v[1] * m[1][2] + v[2] * m[2][2];
+
+    Vec3::new(x, y, z)
+}
+
+pub type Mat4d = Matrix4<f64>;
+pub type Mat4f = Matrix4<f32>;
+
+pub type Mat3d = Matrix3<f64>;
+pub type Mat3f = Matrix3<f32>;
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        mul_point_matrix, mul_vec_matrix, mul_vec_matrix3, multiply, Mat3d, Mat4d, Mat4f, Matrix,
+        Matrix3, Matrix4,
+    };
+    use crate::assert_approx_eq;
+    use crate::{ApproxEq, Planed, Point3, Quatd, Tuple, Vec3, Vec4};
+
+    #[test]
+    fn create_matrix() {
+        let m1 = Mat4d {
+            ..Default::default()
+        };
+        for i in 0..4 {
+            for j in 0..4 {
+                assert_eq!(m1[i][j], 0f64);
+            }
+        }
+
+        let m2 = Mat4d::new(
+            1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0, 11.0, 12.0, 13.0, 14.0, 15.0, 16.0,
+        );
+        assert_eq!(m2[0][0], 1.0);
+        assert_eq!(m2[0][1], 2.0);
+        assert_eq!(m2[0][2], 3.0);
+        assert_eq!(m2[0][3], 4.0);
+        assert_eq!(m2[1][0], 5.0);
+        assert_eq!(m2[1][1], 6.0);
+        assert_eq!(m2[1][2], 7.0);
+        assert_eq!(m2[1][3], 8.0);
+        assert_eq!(m2[2][0], 9.0);
+        assert_eq!(m2[2][1], 10.0);
+        assert_eq!(m2[2][2], 11.0);
+        assert_eq!(m2[2][3], 12.0);
+        assert_eq!(m2[3][0], 13.0);
+        assert_eq!(m2[3][1], 14.0);
+        assert_eq!(m2[3][2], 15.0);
+        assert_eq!(m2[3][3], 16.0);
+    }
+
+    #[test]
+    fn multiply_matrieces() {
+        let m1 = Matrix4::new(5, 7, 9, 10, 2, 3, 3, 8, 8, 10, 2, 3, 3, 3, 4, 8);
+        let m2 = Matrix4::new(3, 10, 12, 18, 12, 1, 4, 9, 9, 10, 12, 2, 3, 12, 4, 10);
+        let result = multiply(&m1, &m2);
+        assert_eq!(result[0][0], 210);
+        assert_eq!(result[0][1], 267);
+        assert_eq!(result[0][2], 236);
+        assert_eq!(result[0][3], 271);
+        assert_eq!(result[1][0], 93);
+        assert_eq!(result[1][1], 149);
+        assert_eq!(result[1][2], 104);
+        assert_eq!(result[1][3], 149);
+        assert_eq!(result[2][0], 171);
+        assert_eq!(result[2][1], 146);
+        assert_eq!(result[2][2], 172);
+        assert_eq!(result[2][3], 268);
+        assert_eq!(result[3][0], 105);
+        assert_eq!(result[3][1], 169);
+        assert_eq!(result[3][2], 128);
+        assert_eq!(result[3][3], 169);
+
+        let result = m1 * m2;
+        assert_eq!(result[0][0], 210);
+        assert_eq!(result[0][1], 267);
+        assert_eq!(result[0][2], 236);
+        assert_eq!(result[0][3], 271);
+        assert_eq!(result[1][0], 93);
+        assert_eq!(result[1][1], 149);
+        assert_eq!(result[1][2], 104);
+        assert_eq!(result[1][3], 149);
+        assert_eq!(result[2][0], 171);
+        assert_eq!(result[2][1], 146);
+        assert_eq!(result[2][2], 172);
+        assert_eq!(result[2][3], 268);
+        assert_eq!(result[3][0], 105);
+        assert_eq!(result[3][1], 169);
+        assert_eq!(result[3][2], 128);
+        assert_eq!(result[3][3], 169);
+    }
+
+    #[test]
+    fn checked_multiply() {
+        let m1 = Matrix4::new(5, 7, 9, 10, 2, 3, 3, 8, 8, 10, 2, 3, 3, 3, 4, 8);
+        let m2 = Matrix4::new(3, 10, 12, 18, 12, 1, 4, 9, 9, 10, 12, 2, 3, 12, 4, 10);
+        assert_eq!(Matrix4::checked_mul(&m1, &m2), Some(m1 * m2));
+
+        let mut big: Matrix4<i32> = Matrix4::identity();
+        big[0][0] = 1 << 16;
+        assert_eq!(Matrix4::checked_mul(&big, &big), None);
+
+        // Each product fits, but their sum does not.
+        let big = Matrix4::<u8>::new(16, 16, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0);
+        let tall = Matrix4::<u8>::new(8, 0, 0, 0, 8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0);
+        assert_eq!(Matrix4::checked_mul(&big, &tall), None);
+        assert_eq!(Matrix4::checked_mul(&tall, &big).unwrap()[0][0], 128);
+    }
+
+    #[test]
+    fn add_and_subtract_matrices() {
+        let m1 = Matrix4::new(5, 7, 9, 10, 2, 3, 3, 8, 8, 10, 2, 3, 3, 3, 4, 8);
+        let m2 = Matrix4::new(3, 10, 12, 18, 12, 1, 4, 9, 9, 10, 12, 2, 3, 12, 4, 10);
+
+        let sum = m1 + m2;
+        let expected = Matrix4::new(8, 17, 21, 28, 14, 4, 7, 17, 17, 20, 14, 5, 6, 15, 8, 18);
+        for i in 0..4 {
+            for j in 0..4 {
+                assert_eq!(sum[i][j], m1[i][j] + m2[i][j]);
+            }
+        }
+        assert_eq!(sum, expected);
+
+        let diff = m1 - m2;
+        let expected = Matrix4::new(2, -3, -3, -8, -10, 2, -1, -1, -1, 0, -10, 1, 0, -9, 0, -2);
+        for i in 0..4 {
+            for j in 0..4 {
+                assert_eq!(diff[i][j], m1[i][j] - m2[i][j]);
+            }
+        }
+        assert_eq!(diff, expected);
+
+        let mut m = m1;
+        m += m2;
+        assert_eq!(m, sum);
+        m -= m2;
+        assert_eq!(m, m1);
+        m -= m2;
+        assert_eq!(m, diff);
+    }
+
+    #[test]
+    fn scalar_multiplication() {
+        let m = Matrix4::new(5, 7, 9, 10, 2, 3, 3, 8, 8, 10, 2, 3, 3, 3, 4, -8);
+        let scaled = m * 2;
+        let expected = Matrix4::new(10, 14, 18, 20, 4, 6, 6, 16, 16, 20, 4, 6, 6, 6, 8, -16);
+        assert_eq!(scaled, expected);
+        assert_eq!(m * 1, m);
+
+        let average = (Mat4d::identity() + Mat4d::scaling(3.0, 3.0, 3.0)) * 0.5;
+        assert_eq!(average, Mat4d::scaling(2.0, 2.0, 2.0));
+    }
+
+    #[test]
+    fn from_rows_and_cols() {
+        let rows = [
+            Vec4::new(5, 7, 9, 10),
+            Vec4::new(2, 3, 3, 8),
+            Vec4::new(8, 10, 2, 3),
+            Vec4::new(3, 3, 4, 8),
+        ];
+        let m = Matrix4::from_rows(rows);
+        assert_eq!(
+            m,
+            Matrix4::new(5, 7, 9, 10, 2, 3, 3, 8, 8, 10, 2, 3, 3, 3, 4, 8)
+        );
+        for (i, row) in rows.iter().enumerate() {
+            assert!(Vec4::new(m[i][0], m[i][1], m[i][2], m[i][3]) == *row);
+        }
+
+        let m = Matrix4::from_cols(rows);
+        assert_eq!(m, Matrix4::from_rows(rows).transposed());
+        for (j, col) in rows.iter().enumerate() {
+            assert!(Vec4::new(m[0][j], m[1][j], m[2][j], m[3][j]) == *col);
+        }
+    }
+
+    #[test]
+    fn flat_array_round_trip() {
+        let m = Matrix4::new(1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16);
+
+        let rows = m.to_array();
+        assert_eq!(
+            rows,
+            [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]
+        );
+        assert_eq!(Matrix4::from_array(rows), m);
+
+        let cols = m.to_array_col_major();
+        assert_eq!(
+            cols,
+            [1, 5, 9, 13, 2, 6, 10, 14, 3, 7, 11, 15, 4, 8, 12, 16]
+        );
+        assert_eq!(Matrix4::from_array_col_major(cols), m);
+
+        let translation = Mat4f::translation(1.0, 2.0, 3.0);
+        assert_eq!(&translation.to_array()[12..15], &[1.0, 2.0, 3.0]);
+        assert_eq!(translation.to_array_col_major()[3], 1.0);
+    }
+
+    #[test]
+    fn row_and_column_access() {
+        let mut m = Matrix4::new(5, 7, 9, 10, 2, 3, 3, 8, 8, 10, 2, 3, 3, 3, 4, 8);
+        assert!(m.row(1) == Vec4::new(2, 3, 3, 8));
+        assert!(m.column(2) == Vec4::new(9, 3, 2, 4));
+        assert!(m.transposed().row(2) == m.column(2));
+
+        m.set_row(0, &Vec4::new(1, 2, 3, 4));
+        assert!(m.row(0) == Vec4::new(1, 2, 3, 4));
+        m.set_column(3, &Vec4::new(-1, -2, -3, -4));
+        assert!(m.column(3) == Vec4::new(-1, -2, -3, -4));
+        assert_eq!(
+            m,
+            Matrix4::new(1, 2, 3, -1, 2, 3, 3, -2, 8, 10, 2, -3, 3, 3, 4, -4)
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn row_out_of_bounds() {
+        let m: Matrix4<i32> = Matrix4::identity();
+        let _ = m.row(4);
+    }
+
+    #[test]
+    fn transpose_test() {
+        let mut m = Matrix4::new(5, 7, 9, 10, 2, 3, 3, 8, 8, 10, 2, 3, 3, 3, 4, 8);
+        m.transpose();
+        assert_eq!(m[0][0], 5);
+        assert_eq!(m[0][1], 2);
         assert_eq!(m[0][2], 8);
         assert_eq!(m[0][3], 3);
         assert_eq!(m[1][0], 7);
@@ -280,4 +1499,727 @@ mod tests {
         assert_eq!(transposed[3][2], 4);
         assert_eq!(transposed[3][3], 8);
     }
+
+    #[test]
+    fn inverse_test() {
+        let m = Mat4d::new(
+            2.0, 0.0, 0.0, 0.0, 0.0, 4.0, 0.0, 0.0, 0.0, 0.0, 8.0, 0.0, 1.0, 2.0, 3.0, 1.0,
+        );
+        let inv = m.inversed().unwrap();
+        assert_approx_eq!(inv[0][0], 0.5, 1e-9);
+        assert_approx_eq!(inv[1][1], 0.25, 1e-9);
+        assert_approx_eq!(inv[2][2], 0.125, 1e-9);
+        assert_approx_eq!(inv[3][0], -0.5, 1e-9);
+        assert_approx_eq!(inv[3][1], -0.5, 1e-9);
+        assert_approx_eq!(inv[3][2], -0.375, 1e-9);
+        assert_approx_eq!(inv[3][3], 1.0, 1e-9);
+
+        let result = m * inv;
+        for i in 0..4 {
+            for j in 0..4 {
+                let expected = if i == j { 1.0 } else { 0.0 };
+                assert_approx_eq!(result[i][j], expected, 1e-9);
+            }
+        }
+
+        let mut m = Mat4d::new(
+            0.0, 1.0, 2.0, 3.0, 1.0, 0.0, 4.0, 2.0, 3.0, 5.0, 1.0, 0.0, 2.0, 2.0, 0.0, 1.0,
+        );
+        let inv = m.inversed().unwrap();
+        assert!(m.inverse());
+        for i in 0..4 {
+            for j in 0..4 {
+                assert_eq!(m[i][j], inv[i][j]);
+            }
+        }
+    }
+
+    #[test]
+    fn inverse_affine_matches_general_inverse() {
+        let m = Mat4d::scaling(2.0, 0.5, 4.0)
+            * Mat4d::rotation_axis(&Vec3::new(1.0, 2.0, -1.0), 0.8)
+            * Mat4d::translation(3.0, -1.0, 2.5);
+        let inv = m.inverse_affine().unwrap();
+        assert!(inv.approx_eq(&m.inversed().unwrap(), 1e-12));
+        assert!((m * inv).approx_eq(&Mat4d::identity(), 1e-12));
+
+        assert!(Mat4d::scaling(1.0, 0.0, 1.0).inverse_affine().is_none());
+    }
+
+    #[test]
+    fn inverse_rotation_is_transpose() {
+        let m = Mat4d::rotation_z(0.6);
+        let inv = m.inverse_rotation();
+        assert_eq!(inv, Mat4d::rotation_z(-0.6));
+        assert!(inv.approx_eq(&m.inversed().unwrap(), 1e-12));
+        assert!((m * inv).approx_eq(&Mat4d::identity(), 1e-12));
+    }
+
+    #[test]
+    #[cfg(all(feature = "debug-checks", debug_assertions))]
+    #[should_panic(expected = "not a pure rotation")]
+    fn inverse_rotation_rejects_scale() {
+        Mat4d::scaling(2.0, 1.0, 1.0).inverse_rotation();
+    }
+
+    #[test]
+    fn inverse_singular_matrix() {
+        let mut m = Mat4d::new(
+            1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 2.0, 4.0, 6.0, 8.0, 0.0, 0.0, 0.0, 1.0,
+        );
+        assert!(m.inversed().is_none());
+        assert!(!m.inverse());
+        assert_eq!(m[0][0], 1.0);
+        assert_eq!(m[1][1], 6.0);
+        assert_eq!(m[2][2], 6.0);
+        assert_eq!(m[3][3], 1.0);
+    }
+
+    #[test]
+    fn inverse_tolerance_scales_with_matrix() {
+        let tiny = Mat4d::rotation_x(0.3) * Mat4d::translation(1.0, 2.0, 3.0) * 1e-20;
+        let inv = tiny.inversed().unwrap();
+        assert!((tiny * inv).approx_eq(&Mat4d::identity(), 1e-12));
+
+        // The determinant underflows to exactly zero at this scale.
+        let tinier = Mat4d::identity() * 1e-100;
+        assert_eq!(tinier.determinant(), 0.0);
+        assert_eq!(tinier.inversed(), Some(Mat4d::identity() * 1e100));
+        let tinier = Mat4d::rotation_x(0.3) * Mat4d::translation(1.0, 2.0, 3.0) * 1e-100;
+        let inv = tinier.inversed().unwrap();
+        assert!((tinier * inv).approx_eq(&Mat4d::identity(), 1e-12));
+
+        // The third row is r0 + r1 up to f32 rounding, so the determinant is
+        // not exactly zero but the matrix is numerically singular.
+        let (a, b) = (0.1f32, 0.7f32);
+        let near_singular = Mat4f::from_rows([
+            Vec4::new(a, 0.2, 0.3, 0.0),
+            Vec4::new(b, 0.5, 0.9, 0.0),
+            Vec4::new(a + b, 0.2 + 0.5, 0.3 + 0.9, 0.0),
+            Vec4::new(0.0, 0.0, 0.0, 1.0),
+        ]);
+        assert!(near_singular.inversed().is_none());
+        assert!(Mat4f::rotation_z(0.3).inversed().is_some());
+    }
+
+    #[test]
+    fn identity_matrix() {
+        let identity: Mat4d = Matrix4::identity();
+        for i in 0..4 {
+            for j in 0..4 {
+                assert_eq!(identity[i][j], if i == j { 1.0 } else { 0.0 });
+            }
+        }
+
+        let m = Matrix4::new(5, 7, 9, 10, 2, 3, 3, 8, 8, 10, 2, 3, 3, 3, 4, 8);
+        let identity: Matrix4<i32> = Matrix4::identity();
+        assert!(identity * m == m);
+        assert!(m * identity == m);
+
+        let m = Mat4f::new(
+            1.5, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0, 11.0, 12.0, 13.0, 14.0, 15.0, 16.0,
+        );
+        assert!(Mat4f::identity() * m == m);
+    }
+
+    #[test]
+    fn determinant_test() {
+        let m = Matrix4::new(5, 7, 9, 10, 2, 3, 3, 8, 8, 10, 2, 3, 3, 3, 4, 8);
+        assert_eq!(m.determinant(), -361);
+        assert_eq!(m.transposed().determinant(), -361);
+        assert_eq!(Matrix4::<i32>::identity().determinant(), 1);
+
+        let singular = Matrix4::new(1, 2, 3, 4, 5, 6, 7, 8, 2, 4, 6, 8, 0, 0, 0, 1);
+        assert_eq!(singular.determinant(), 0);
+
+        let scale = Mat4d::new(
+            2.0, 0.0, 0.0, 0.0, 0.0, 4.0, 0.0, 0.0, 0.0, 0.0, 8.0, 0.0, 1.0, 2.0, 3.0, 1.0,
+        );
+        assert_approx_eq!(scale.determinant(), 64.0, 1e-12);
+    }
+
+    #[test]
+    fn finite_and_nan() {
+        let mut m = Mat4d::rotation_x(0.3);
+        assert!(m.is_finite());
+        assert!(!m.is_nan());
+
+        m[2][1] = f64::INFINITY;
+        assert!(!m.is_finite());
+        assert!(!m.is_nan());
+
+        m[0][3] = f64::NAN;
+        assert!(m.is_nan());
+        assert!(Mat4f::perspective(1.0, 1.0, 0.1, 100.0).is_finite());
+    }
+
+    #[test]
+    fn trace_test() {
+        let m = Matrix4::new(5, 7, 9, 10, 2, 3, 3, 8, 8, 10, 2, 3, 3, 3, 4, 8);
+        assert_eq!(m.trace(), 18);
+        assert_eq!(m.transposed().trace(), 18);
+        assert_eq!(Matrix4::<i32>::identity().trace(), 4);
+        assert_eq!(Mat4d::scaling(2.0, 3.0, 4.0).trace(), 10.0);
+    }
+
+    #[test]
+    fn non_square_product() {
+        let a: Matrix<i32, 2, 3> = Matrix {
+            m: [[1, 2, 3], [4, 5, 6]],
+        };
+        let b: Matrix<i32, 3, 2> = Matrix {
+            m: [[7, 8], [9, 10], [11, 12]],
+        };
+
+        let ab = a * b;
+        assert_eq!(
+            ab,
+            Matrix {
+                m: [[58, 64], [139, 154]]
+            }
+        );
+
+        let ba: Matrix<i32, 3, 3> = b * a;
+        assert_eq!(ba, Matrix3::new(39, 54, 69, 49, 68, 87, 59, 82, 105));
+
+        assert_eq!(
+            a.transposed(),
+            Matrix {
+                m: [[1, 4], [2, 5], [3, 6]]
+            }
+        );
+        assert_eq!(Matrix::checked_mul(&a, &b), Some(ab));
+        assert_eq!(Matrix::<i32, 2, 3>::zeros(), Matrix { m: [[0; 3]; 2] });
+        assert_eq!(
+            format!("{:?}", a),
+            "Matrix2x3 [\n    [1, 2, 3],\n    [4, 5, 6],\n]"
+        );
+    }
+
+    #[test]
+    fn matrix3_multiply_and_determinant() {
+        let m1 = Matrix3::new(2, 3, 1, 4, 1, 5, 7, 2, 6);
+        let m2 = Matrix3::new(1, 0, 2, 3, 4, 1, 5, 2, 0);
+        let result = m1 * m2;
+        assert!(result == Matrix3::new(16, 14, 7, 32, 14, 9, 43, 20, 16));
+        let identity: Matrix3<i32> = Matrix3::identity();
+        assert!(identity * m1 == m1);
+
+        assert_eq!(m1.determinant(), 26);
+        assert_eq!(m2.determinant(), -30);
+        assert_eq!(m1.transposed().determinant(), 26);
+
+        let mut m = m1;
+        m.transpose();
+        assert!(m == Matrix3::new(2, 4, 7, 3, 1, 2, 1, 5, 6));
+    }
+
+    #[test]
+    fn matrix3_inverse() {
+        let m = Mat3d::new(2.0, 3.0, 1.0, 4.0, 1.0, 5.0, 7.0, 2.0, 6.0);
+        let result = m * m.inversed().unwrap();
+        for i in 0..3 {
+            for j in 0..3 {
+                let expected = if i == j { 1.0 } else { 0.0 };
+                assert_approx_eq!(result[i][j], expected, 1e-12);
+            }
+        }
+
+        let mut singular = Mat3d::new(1.0, 2.0, 3.0, 2.0, 4.0, 6.0, 0.0, 1.0, 1.0);
+        assert!(singular.inversed().is_none());
+        assert!(!singular.inverse());
+        assert_eq!(singular[1][0], 2.0);
+    }
+
+    #[test]
+    fn normal_transform() {
+        let m = Mat4d::new(
+            2.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 5.0, 6.0, 7.0, 1.0,
+        );
+        let block = m.upper_left_3x3();
+        assert!(block == Mat3d::new(2.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0));
+
+        let normal_matrix = block.inversed().unwrap().transposed();
+        let normal = mul_vec_matrix3(&Vec3::new(1.0, 1.0, 0.0), &normal_matrix);
+        assert_eq!(normal.x, 0.5);
+        assert_eq!(normal.y, 1.0);
+        assert_eq!(normal.z, 0.0);
+    }
+
+    #[test]
+    fn transform_methods() {
+        let m = Mat4d::rotation_axis(&Vec3::new(1.0, -2.0, 0.5), 0.7)
+            * Mat4d::scaling(2.0, 3.0, 0.5)
+            * Mat4d::translation(4.0, -1.0, 2.5);
+        let p = Point3::new(1.5, -0.5, 2.0);
+        let v = Vec3::new(-1.0, 0.25, 3.0);
+
+        assert_eq!(m.transform_point(&p), mul_point_matrix(&p, &m));
+        assert_eq!(m.transform_direction(&v), mul_vec_matrix(&v, &m));
+
+        let translation = Mat4d::translation(1.0, 2.0, 3.0);
+        assert_eq!(translation.transform_direction(&v), v);
+        assert_eq!(translation.transform_point(&p), Point3::new(2.5, 1.5, 5.0));
+    }
+
+    #[test]
+    fn decompose_transform() {
+        let axis = Vec3::new(0.5, -1.0, 2.0);
+        let scaling = Mat4d::scaling(2.0, 0.5, 3.0);
+        let rotation = Mat4d::rotation_axis(&axis, 1.1);
+        let translation = Mat4d::translation(4.0, -5.0, 6.0);
+        let m = scaling * rotation * translation;
+
+        let (t, r, s) = m.decompose();
+        assert!(t.approx_eq(&Vec3::new(4.0, -5.0, 6.0), 1e-12));
+        assert!(s.approx_eq(&Vec3::new(2.0, 0.5, 3.0), 1e-12));
+        assert!(r.to_matrix4().approx_eq(&rotation, 1e-12));
+
+        let rebuilt =
+            Mat4d::scaling(s.x, s.y, s.z) * r.to_matrix4() * Mat4d::translation(t.x, t.y, t.z);
+        assert!(rebuilt.approx_eq(&m, 1e-12));
+
+        let mirrored = Mat4d::scaling(1.0, -2.0, 1.0) * rotation * translation;
+        let (t, r, s) = mirrored.decompose();
+        assert!(t.approx_eq(&Vec3::new(4.0, -5.0, 6.0), 1e-12));
+        assert!(s.approx_eq(&Vec3::new(-1.0, 2.0, 1.0), 1e-12));
+        let rebuilt =
+            Mat4d::scaling(s.x, s.y, s.z) * r.to_matrix4() * Mat4d::translation(t.x, t.y, t.z);
+        assert!(rebuilt.approx_eq(&mirrored, 1e-12));
+    }
+
+    #[test]
+    fn compose_transform() {
+        let translation = Vec3::new(4.0, -5.0, 6.0);
+        let rotation = Quatd::from_axis_angle(&Vec3::new(0.5, -1.0, 2.0), 1.1);
+        let scale = Vec3::new(2.0, 0.5, 3.0);
+
+        let m = Mat4d::compose(&translation, &rotation, &scale);
+        let expected = Mat4d::scaling(2.0, 0.5, 3.0)
+            * rotation.to_matrix4()
+            * Mat4d::translation(4.0, -5.0, 6.0);
+        assert!(m.approx_eq(&expected, 1e-12));
+
+        let (t, r, s) = m.decompose();
+        assert!(t.approx_eq(&translation, 1e-12));
+        assert!(s.approx_eq(&scale, 1e-12));
+        let dot = r.x * rotation.x + r.y * rotation.y + r.z * rotation.z + r.w * rotation.w;
+        assert_approx_eq!(dot.abs(), 1.0, 1e-12);
+    }
+
+    #[test]
+    fn chained_transforms() {
+        let t = Vec3::new(1.0, -2.0, 3.0);
+        let s = Vec3::new(2.0, 0.5, 4.0);
+        let axis = Vec3::new(1.0, 1.0, 0.0);
+
+        let built = Mat4d::identity()
+            .then_translate(&t)
+            .then_rotate_x(0.3)
+            .then_rotate_y(-1.2)
+            .then_rotate_z(0.7)
+            .then_rotate_axis(&axis, 2.0)
+            .then_scale(&s);
+        let expected = Mat4d::translation(1.0, -2.0, 3.0)
+            * Mat4d::rotation_x(0.3)
+            * Mat4d::rotation_y(-1.2)
+            * Mat4d::rotation_z(0.7)
+            * Mat4d::rotation_axis(&axis, 2.0)
+            * Mat4d::scaling(2.0, 0.5, 4.0);
+        assert_eq!(built, expected);
+
+        // Steps apply in call order: translate first, then scale.
+        let m = Mat4d::identity().then_translate(&t).then_scale(&s);
+        let p = m.transform_point(&Point3::new(0.0, 0.0, 0.0));
+        assert_eq!(p, Point3::new(2.0, -1.0, 12.0));
+    }
+
+    #[test]
+    fn mul_assign_left_matches_product() {
+        let a = Matrix4::new(3, -1, 4, 1, 5, 9, -2, 6, 5, 3, 5, -8, 9, 7, 9, 3);
+        let b = Matrix4::new(2, 7, 1, -8, 2, 8, 1, 8, -2, 8, 4, 5, 9, 0, 4, 5);
+        let mut m = b;
+        m.mul_assign_left(&a);
+        assert_eq!(m, a * b);
+        assert_ne!(m, b * a);
+
+        let steps = [
+            Mat4d::translation(1.0, -2.0, 3.0),
+            Mat4d::rotation_x(0.3),
+            Mat4d::scaling(2.0, 0.5, 4.0),
+            Mat4d::rotation_axis(&Vec3::new(1.0, 1.0, 0.0), 2.0),
+        ];
+        let mut acc = Mat4d::identity();
+        let mut expected = Mat4d::identity();
+        for step in &steps {
+            acc.mul_assign_left(step);
+            expected = *step * expected;
+        }
+        assert!(acc.approx_eq(&expected, 1e-12));
+    }
+
+    #[test]
+    fn translation_matrix() {
+        let m = Mat4d::translation(1.0, 2.0, 3.0);
+
+        let p = mul_point_matrix(&Point3::new(0.0, 0.0, 0.0), &m);
+        assert!(p == Point3::new(1.0, 2.0, 3.0));
+
+        let p = mul_point_matrix(&Point3::new(-1.0, 5.0, 0.5), &m);
+        assert!(p == Point3::new(0.0, 7.0, 3.5));
+
+        let p = mul_point_matrix(&Point3::new(10.0, -2.0, -3.0), &m);
+        assert!(p == Point3::new(11.0, 0.0, 0.0));
+
+        let v = mul_vec_matrix(&Vec3::new(1.0, 1.0, 1.0), &m);
+        assert!(v == Vec3::new(1.0, 1.0, 1.0));
+    }
+
+    #[test]
+    fn row_vector_convention() {
+        let offset = Vec3::new(1.5, -2.0, 4.25);
+        let m = Mat4d::translation(offset.x, offset.y, offset.z);
+        assert_eq!(m[3][0], offset.x);
+        assert_eq!(m[3][1], offset.y);
+        assert_eq!(m[3][2], offset.z);
+
+        for p in [
+            Point3::new(0.0, 0.0, 0.0),
+            Point3::new(-3.0, 0.5, 8.0),
+            Point3::new(100.0, -7.25, 0.125),
+        ] {
+            let moved = mul_point_matrix(&p, &m);
+            assert_eq!(moved - p, offset);
+        }
+
+        // The transposed matrix puts the offset in column 3, which this
+        // convention treats as the projective row instead of a translation.
+        let p = Point3::new(1.0, 1.0, 1.0);
+        assert!(mul_point_matrix(&p, &m.transposed()) != p + offset);
+
+        // `a * b` applies `a` first: scale then translate.
+        let scale_then_translate = Mat4d::scaling(2.0, 2.0, 2.0) * m;
+        let moved = mul_point_matrix(&p, &scale_then_translate);
+        assert_eq!(moved, Point3::new(2.0, 2.0, 2.0) + offset);
+    }
+
+    #[test]
+    fn scaling_matrix() {
+        let m = Mat4d::scaling(2.0, 3.0, 4.0);
+
+        let v = mul_vec_matrix(&Vec3::new(1.0, 2.0, 3.0), &m);
+        assert!(v == Vec3::new(2.0, 6.0, 12.0));
+
+        let p = mul_point_matrix(&Point3::new(1.0, 2.0, 3.0), &m);
+        assert!(p == Point3::new(2.0, 6.0, 12.0));
+
+        let m = m * Mat4d::translation(1.0, 1.0, 1.0);
+        let p = mul_point_matrix(&Point3::new(1.0, 2.0, 3.0), &m);
+        assert!(p == Point3::new(3.0, 7.0, 13.0));
+
+        let mut m = Mat4d::scaling(2.0, 3.0, 4.0);
+        m[3][3] = 2.0;
+        let p = mul_point_matrix(&Point3::new(1.0, 2.0, 3.0), &m);
+        assert!(p == Point3::new(1.0, 3.0, 6.0));
+    }
+
+    #[test]
+    fn shear_matrix() {
+        let p = Point3::new(1.0, 2.0, 3.0);
+
+        let m = Mat4d::shear(0.5, 0.0, 0.0, 0.0, 0.0, 0.0);
+        assert_eq!(m.transform_point(&p), Point3::new(2.0, 2.0, 3.0));
+
+        let m = Mat4d::shear(1.0, 2.0, 3.0, 4.0, 5.0, 6.0);
+        let expected = Point3::new(1.0 + 2.0 + 6.0, 2.0 + 3.0 + 12.0, 3.0 + 5.0 + 12.0);
+        assert_eq!(m.transform_point(&p), expected);
+        assert_eq!(
+            m.transform_direction(&Vec3::from_point(&p)),
+            Vec3::from_point(&expected)
+        );
+
+        assert_eq!(
+            Mat4d::shear(0.0, 0.0, 0.0, 0.0, 0.0, 0.0),
+            Mat4d::identity()
+        );
+        assert_eq!(
+            Mat4d::shear(0.3, 0.0, 0.0, 0.0, 0.0, 0.0).determinant(),
+            1.0
+        );
+    }
+
+    #[test]
+    fn rotation_matrices() {
+        use std::f64::consts::PI;
+
+        let v = mul_vec_matrix(&Vec3::new(1.0, 0.0, 0.0), &Mat4d::rotation_z(PI / 2.0));
+        assert_approx_eq!(v.x, 0.0);
+        assert_approx_eq!(v.y, 1.0);
+        assert_approx_eq!(v.z, 0.0);
+
+        let v = mul_vec_matrix(&Vec3::new(0.0, 1.0, 0.0), &Mat4d::rotation_x(PI / 2.0));
+        assert_approx_eq!(v.x, 0.0);
+        assert_approx_eq!(v.y, 0.0);
+        assert_approx_eq!(v.z, 1.0);
+
+        let v = mul_vec_matrix(&Vec3::new(0.0, 0.0, 1.0), &Mat4d::rotation_y(PI / 2.0));
+        assert_approx_eq!(v.x, 1.0);
+        assert_approx_eq!(v.y, 0.0);
+        assert_approx_eq!(v.z, 0.0);
+
+        let m = Mat4f::rotation_z(std::f32::consts::PI / 2.0);
+        let v = mul_vec_matrix(&Vec3::new(1.0f32, 0.0, 0.0), &m);
+        assert_approx_eq!(v.x, 0.0);
+        assert_approx_eq!(v.y, 1.0);
+    }
+
+    #[test]
+    fn axis_angle_rotation() {
+        use std::f64::consts::PI;
+
+        let axis = Vec3::new(1.0, 1.0, 1.0);
+        let m = Mat4d::rotation_axis(&axis, 2.0 * PI / 3.0);
+
+        let v = mul_vec_matrix(&Vec3::new(1.0, 0.0, 0.0), &m);
+        assert_approx_eq!(v.x, 0.0);
+        assert_approx_eq!(v.y, 1.0);
+        assert_approx_eq!(v.z, 0.0);
+
+        let v = mul_vec_matrix(&Vec3::new(0.0, 2.0, 0.0), &m);
+        assert_approx_eq!(v.x, 0.0);
+        assert_approx_eq!(v.y, 0.0);
+        assert_approx_eq!(v.z, 2.0);
+
+        let v = mul_vec_matrix(&axis, &m);
+        assert_approx_eq!(v.x, 1.0);
+        assert_approx_eq!(v.y, 1.0);
+        assert_approx_eq!(v.z, 1.0);
+
+        let m = Mat4d::rotation_axis(&Vec3::new(0.0, 0.0, 3.0), 0.7);
+        let expected = Mat4d::rotation_z(0.7);
+        for i in 0..4 {
+            for j in 0..4 {
+                assert_approx_eq!(m[i][j], expected[i][j]);
+            }
+        }
+    }
+
+    #[test]
+    fn euler_round_trip() {
+        let m = Mat4d::from_euler_xyz(0.3, -0.7, 1.2);
+        let expected = Mat4d::rotation_x(0.3) * Mat4d::rotation_y(-0.7) * Mat4d::rotation_z(1.2);
+        assert!(m.approx_eq(&expected, 1e-12));
+
+        let (x, y, z) = m.to_euler_xyz();
+        assert_approx_eq!(x, 0.3, 1e-12);
+        assert_approx_eq!(y, -0.7, 1e-12);
+        assert_approx_eq!(z, 1.2, 1e-12);
+
+        let (x, y, z) = Mat4d::identity().to_euler_xyz();
+        assert_eq!((x, y, z), (0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn euler_gimbal_lock() {
+        use std::f64::consts::FRAC_PI_2;
+
+        for pitch in [FRAC_PI_2, -FRAC_PI_2] {
+            let m = Mat4d::from_euler_xyz(0.4, pitch, 0.3);
+            let (x, y, z) = m.to_euler_xyz();
+            assert_approx_eq!(y, pitch, 1e-6);
+            assert_eq!(z, 0.0);
+            assert!(Mat4d::from_euler_xyz(x, y, z).approx_eq(&m, 1e-6));
+        }
+    }
+
+    #[test]
+    fn reflection_matrix() {
+        let xy = Planed::from_point_normal(&Point3::new(0.0, 0.0, 0.0), &Vec3::unit_z());
+        let m = Mat4d::reflection(&xy);
+        assert_eq!(m, Mat4d::scaling(1.0, 1.0, -1.0));
+        let p = Point3::new(1.0, -2.0, 3.0);
+        assert_eq!(m.transform_point(&p), Point3::new(1.0, -2.0, -3.0));
+
+        let plane =
+            Planed::from_point_normal(&Point3::new(1.0, 1.0, 0.0), &Vec3::new(1.0, 1.0, 0.0));
+        let m = Mat4d::reflection(&plane);
+        let q = m.transform_point(&p);
+        assert_approx_eq!(plane.signed_distance(&q), -plane.signed_distance(&p), 1e-12);
+        assert!(Point3::lerp(&p, &q, 0.5).approx_eq(&plane.project_point(&p), 1e-12));
+        assert!((m * m).approx_eq(&Mat4d::identity(), 1e-12));
+        assert_approx_eq!(m.determinant(), -1.0, 1e-12);
+    }
+
+    #[test]
+    fn perspective_projection() {
+        use std::f64::consts::PI;
+
+        let m = Mat4d::perspective(PI / 2.0, 2.0, 0.5, 100.0);
+
+        let p = mul_point_matrix(&Point3::new(0.0, 0.0, -0.5), &m);
+        assert_approx_eq!(p.z, -1.0);
+
+        let p = mul_point_matrix(&Point3::new(0.0, 0.0, -100.0), &m);
+        assert_approx_eq!(p.z, 1.0);
+
+        let p = mul_point_matrix(&Point3::new(1.0, 0.5, -0.5), &m);
+        assert_approx_eq!(p.x, 1.0);
+        assert_approx_eq!(p.y, 1.0);
+
+        let m = Mat4f::perspective(std::f32::consts::PI / 3.0, 1.0, 1.0, 10.0);
+        let p = mul_point_matrix(&Point3::new(0.0f32, 0.0, -1.0), &m);
+        assert_approx_eq!(p.z, -1.0f32);
+    }
+
+    #[test]
+    fn orthographic_projection() {
+        let m = Mat4d::orthographic(-2.0, 4.0, -1.0, 3.0, 0.5, 10.0);
+
+        for (x, nx) in [(-2.0, -1.0), (4.0, 1.0)] {
+            for (y, ny) in [(-1.0, -1.0), (3.0, 1.0)] {
+                for (z, nz) in [(-0.5, -1.0), (-10.0, 1.0)] {
+                    let p = mul_point_matrix(&Point3::new(x, y, z), &m);
+                    assert_approx_eq!(p.x, nx);
+                    assert_approx_eq!(p.y, ny);
+                    assert_approx_eq!(p.z, nz);
+                }
+            }
+        }
+
+        let p = mul_point_matrix(&Point3::new(1.0, 1.0, -5.25), &m);
+        assert_approx_eq!(p.x, 0.0);
+        assert_approx_eq!(p.y, 0.0);
+        assert_approx_eq!(p.z, 0.0);
+    }
+
+    #[test]
+    fn frustum_corners_of_orthographic() {
+        let proj = Mat4d::orthographic(-2.0, 2.0, -1.0, 1.0, 1.0, 10.0);
+        let corners = proj.frustum_corners().unwrap();
+        let expected = [
+            Point3::new(-2.0, -1.0, -1.0),
+            Point3::new(2.0, -1.0, -1.0),
+            Point3::new(-2.0, 1.0, -1.0),
+            Point3::new(2.0, 1.0, -1.0),
+            Point3::new(-2.0, -1.0, -10.0),
+            Point3::new(2.0, -1.0, -10.0),
+            Point3::new(-2.0, 1.0, -10.0),
+            Point3::new(2.0, 1.0, -10.0),
+        ];
+        for (corner, expected) in corners.iter().zip(&expected) {
+            assert!(corner.approx_eq(expected, 1e-12));
+        }
+
+        // A camera moved to x = 3 sees the same box shifted along x.
+        let view_proj = Mat4d::translation(-3.0, 0.0, 0.0) * proj;
+        let corners = view_proj.frustum_corners().unwrap();
+        for (corner, expected) in corners.iter().zip(&expected) {
+            let shifted = *expected + Vec3::new(3.0, 0.0, 0.0);
+            assert!(corner.approx_eq(&shifted, 1e-12));
+        }
+    }
+
+    #[test]
+    fn frustum_corners_of_singular_matrix() {
+        assert!(Mat4d::zeros().frustum_corners().is_none());
+
+        // Flattening z before projecting collapses the frustum to a plane.
+        let proj = Mat4d::orthographic(-2.0, 2.0, -1.0, 1.0, 1.0, 10.0);
+        let flattened = Mat4d::scaling(1.0, 1.0, 0.0) * proj;
+        assert!(flattened.frustum_corners().is_none());
+    }
+
+    #[test]
+    fn look_at_view_matrix() {
+        let eye = Point3::new(1.0, 2.0, 3.0);
+        let target = Point3::new(4.0, 6.0, 3.0);
+        let m = Mat4d::look_at(&eye, &target, &Vec3::new(0.0, 0.0, 1.0));
+
+        let p = mul_point_matrix(&target, &m);
+        assert_approx_eq!(p.x, 0.0);
+        assert_approx_eq!(p.y, 0.0);
+        assert_approx_eq!(p.z, -5.0);
+
+        let p = mul_point_matrix(&eye, &m);
+        assert_approx_eq!(p.x, 0.0);
+        assert_approx_eq!(p.y, 0.0);
+        assert_approx_eq!(p.z, 0.0);
+
+        let p = mul_point_matrix(&Point3::new(1.0, 2.0, 4.0), &m);
+        assert_approx_eq!(p.x, 0.0);
+        assert_approx_eq!(p.y, 1.0);
+        assert_approx_eq!(p.z, 0.0);
+    }
+
+    #[test]
+    fn look_at_parallel_up() {
+        let eye = Point3::new(0.0, 0.0, 0.0);
+        let target = Point3::new(0.0, 10.0, 0.0);
+        let m = Mat4d::look_at(&eye, &target, &Vec3::new(0.0, 1.0, 0.0));
+
+        let p = mul_point_matrix(&target, &m);
+        assert_approx_eq!(p.x, 0.0);
+        assert_approx_eq!(p.y, 0.0);
+        assert_approx_eq!(p.z, -10.0);
+
+        let block = m.upper_left_3x3();
+        assert_approx_eq!(block.determinant(), 1.0);
+    }
+
+    #[test]
+    fn checked_access() {
+        let m = Matrix4::new(5, 7, 9, 10, 2, 3, 3, 8, 8, 10, 2, 3, 3, 3, 4, 8);
+        assert_eq!(m.get(1), Some(&[2, 3, 3, 8]));
+        assert_eq!(m.get(4), None);
+        assert_eq!(m.get_element(3, 3), Some(&8));
+        assert_eq!(m.get_element(2, 1), Some(&10));
+        assert_eq!(m.get_element(4, 0), None);
+        assert_eq!(m.get_element(0, 4), None);
+    }
+
+    #[test]
+    fn debug_format() {
+        let m = Matrix4::new(5, 7, 9, 10, 2, 3, 3, 8, 8, 10, 2, 3, 3, 3, 4, 8);
+        let expected = "Matrix4 [\n    [5, 7, 9, 10],\n    [2, 3, 3, 8],\n    [8, 10, 2, 3],\n    [3, 3, 4, 8],\n]";
+        assert_eq!(format!("{:?}", m), expected);
+    }
+
+    #[test]
+    fn display_format() {
+        let m = Mat4d::new(
+            1.0, -2.0, 0.5, 10.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 100.0, 0.0, 0.0, 1.0,
+        );
+        let expected = "[  1.00,  -2.00,   0.50,  10.00]\n\
+                        [  0.00,   1.00,   0.00,   0.00]\n\
+                        [  0.00,   0.00,   1.00,   0.00]\n\
+                        [100.00,   0.00,   0.00,   1.00]";
+        assert_eq!(format!("{:.2}", m), expected);
+
+        let m = Matrix4::new(5, 7, 9, 10, 2, 3, 3, 8, 8, 10, 2, 3, 3, 3, 4, 8);
+        let expected = "[ 5,  7,  9, 10]\n[ 2,  3,  3,  8]\n[ 8, 10,  2,  3]\n[ 3,  3,  4,  8]";
+        assert_eq!(format!("{}", m), expected);
+    }
+
+    #[test]
+    fn approximate_equality() {
+        let m = Mat4d::rotation_z(std::f64::consts::PI / 2.0);
+        let expected = Mat4d::new(
+            0.0, 1.0, 0.0, 0.0, -1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0,
+        );
+        assert!(m.approx_eq(&expected, 1e-12));
+
+        let mut off = expected;
+        off[3][2] = 1.1e-6;
+        assert!(!off.approx_eq(&expected, 1e-6));
+        assert!(off.approx_eq(&expected, 1e-5));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let m = Matrix4::new(5, 7, 9, 10, 2, 3, 3, 8, 8, 10, 2, 3, 3, 3, 4, 8);
+        let json = serde_json::to_string(&m).unwrap();
+        assert_eq!(json, "[[5,7,9,10],[2,3,3,8],[8,10,2,3],[3,3,4,8]]");
+        assert_eq!(serde_json::from_str::<Matrix4<i32>>(&json).unwrap(), m);
+    }
 }
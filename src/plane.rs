@@ -0,0 +1,154 @@
+use core::ops::{Add, Div, Mul, MulAssign, Neg, Sub};
+
+use crate::{One, Point3, Ray, Sqrt, Vec3, Zero};
+
+/// Plane of the points `p` with `normal . p + d = 0`. The constructors keep
+/// `normal` at unit length, so `signed_distance` is a true distance.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Plane<T> {
+    pub normal: Vec3<T>,
+    pub d: T,
+}
+
+impl<T> Plane<T> {
+    /// Plane through `p` facing along a normalized copy of `n`.
+    pub fn from_point_normal(p: &Point3<T>, n: &Vec3<T>) -> Self
+    where
+        T: Copy
+            + Add<Output = T>
+            + Mul<Output = T>
+            + Div<Output = T>
+            + Neg<Output = T>
+            + MulAssign
+            + Zero
+            + One
+            + Sqrt
+            + PartialOrd,
+    {
+        let normal = n.normalized();
+        Self {
+            normal,
+            d: -normal.dot(&Vec3::from_point(p)),
+        }
+    }
+
+    /// Plane through the triangle `abc`, facing the side from which the
+    /// vertices appear counter-clockwise. The points must not be collinear.
+    pub fn from_three_points(a: &Point3<T>, b: &Point3<T>, c: &Point3<T>) -> Self
+    where
+        T: Copy
+            + Add<Output = T>
+            + Sub<Output = T>
+            + Mul<Output = T>
+            + Div<Output = T>
+            + Neg<Output = T>
+            + MulAssign
+            + Zero
+            + One
+            + Sqrt
+            + PartialOrd,
+    {
+        Self::from_point_normal(a, &Vec3::triangle_normal(a, b, c))
+    }
+
+    /// Distance from the plane, positive on the side the normal points to.
+    pub fn signed_distance(&self, p: &Point3<T>) -> T
+    where
+        T: Copy + Add<Output = T> + Mul<Output = T>,
+    {
+        self.normal.dot(&Vec3::from_point(p)) + self.d
+    }
+
+    /// Closest point on the plane to `p`.
+    pub fn project_point(&self, p: &Point3<T>) -> Point3<T>
+    where
+        T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+    {
+        *p - self.normal * self.signed_distance(p)
+    }
+
+    /// Ray parameter `t >= 0` at which `ray` crosses the plane, or `None`
+    /// when the ray is parallel to it or points away from it.
+    pub fn intersect_ray(&self, ray: &Ray<T>) -> Option<T>
+    where
+        T: Copy
+            + Default
+            + Add<Output = T>
+            + Mul<Output = T>
+            + Div<Output = T>
+            + Neg<Output = T>
+            + PartialOrd,
+    {
+        let denom = self.normal.dot(&ray.direction);
+        if denom == T::default() {
+            return None;
+        }
+        let t = -self.signed_distance(&ray.origin) / denom;
+        if t < T::default() {
+            None
+        } else {
+            Some(t)
+        }
+    }
+}
+
+pub type Planed = Plane<f64>;
+pub type Planef = Plane<f32>;
+
+#[cfg(test)]
+mod tests {
+    use super::Planed;
+    use crate::{assert_approx_eq, ApproxEq, Point3, Rayd, Tuple, Vec3};
+
+    #[test]
+    fn from_point_normal() {
+        let p = Point3::new(1.0, 2.0, 3.0);
+        let plane = Planed::from_point_normal(&p, &Vec3::new(0.0, 0.0, 2.0));
+        assert_eq!(plane.normal, Vec3::new(0.0, 0.0, 1.0));
+        assert_eq!(plane.d, -3.0);
+
+        assert_eq!(plane.signed_distance(&p), 0.0);
+        assert_eq!(plane.signed_distance(&Point3::new(-4.0, 5.0, 3.0)), 0.0);
+        assert_eq!(plane.signed_distance(&Point3::new(1.0, 2.0, 4.0)), 1.0);
+        assert_eq!(plane.signed_distance(&Point3::new(0.0, 0.0, 0.5)), -2.5);
+    }
+
+    #[test]
+    fn from_three_points() {
+        let a = Point3::new(1.0, 0.0, 0.0);
+        let b = Point3::new(0.0, 1.0, 0.0);
+        let c = Point3::new(0.0, 0.0, 1.0);
+        let plane = Planed::from_three_points(&a, &b, &c);
+
+        let n = 1.0 / 3.0f64.sqrt();
+        assert!(plane.normal.approx_eq(&Vec3::new(n, n, n), 1e-12));
+        for p in [a, b, c] {
+            assert_approx_eq!(plane.signed_distance(&p), 0.0, 1e-12);
+        }
+        let above = a + plane.normal;
+        assert_approx_eq!(plane.signed_distance(&above), 1.0, 1e-12);
+    }
+
+    #[test]
+    fn project_point() {
+        let plane = Planed::from_point_normal(&Point3::new(0.0, 1.0, 0.0), &Vec3::unit_y());
+        let projected = plane.project_point(&Point3::new(3.0, -4.0, 5.0));
+        assert_eq!(projected, Point3::new(3.0, 1.0, 5.0));
+        assert_eq!(plane.project_point(&projected), projected);
+    }
+
+    #[test]
+    fn intersect_ray() {
+        let plane = Planed::from_point_normal(&Point3::new(0.0, 0.0, 0.0), &Vec3::unit_z());
+
+        let ray = Rayd::new(Point3::new(0.0, 0.0, 2.0), Vec3::new(0.0, 1.0, -1.0));
+        let t = plane.intersect_ray(&ray).unwrap();
+        assert_approx_eq!(t, 2.0 * 2.0f64.sqrt(), 1e-12);
+        assert!(ray.at(t).approx_eq(&Point3::new(0.0, 2.0, 0.0), 1e-12));
+
+        let parallel = Rayd::new(Point3::new(0.0, 0.0, 2.0), Vec3::unit_x());
+        assert_eq!(plane.intersect_ray(&parallel), None);
+        let away = Rayd::new(Point3::new(0.0, 0.0, 2.0), Vec3::unit_z());
+        assert_eq!(plane.intersect_ray(&away), None);
+    }
+}
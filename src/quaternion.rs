@@ -0,0 +1,338 @@
+use core::ops::{Add, Div, Mul, Neg, Sub};
+
+use crate::{Acos, FromF64, Matrix4, One, Sqrt, Trig, Vec3, Vector};
+
+/// Rotation quaternion with vector part `(x, y, z)` and scalar part `w`.
+#[derive(Clone, Copy, PartialEq)]
+pub struct Quaternion<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+    pub w: T,
+}
+
+impl<T> Quaternion<T> {
+    pub fn new(x: T, y: T, z: T, w: T) -> Self
+    where
+        T: Copy,
+    {
+        Self { x, y, z, w }
+    }
+
+    pub fn identity() -> Self
+    where
+        T: Default + One,
+    {
+        Self {
+            x: T::default(),
+            y: T::default(),
+            z: T::default(),
+            w: T::one(),
+        }
+    }
+
+    /// Rotation of `angle` radians about `axis`. The axis is normalized
+    /// internally and must not be zero.
+    pub fn from_axis_angle(axis: &Vec3<T>, angle: T) -> Self
+    where
+        T: Copy + One + Add<Output = T> + Mul<Output = T> + Div<Output = T> + Sqrt + Trig,
+    {
+        let half = angle / (T::one() + T::one());
+        let s = half.sin() / axis.magnitude();
+        Self::new(axis.x * s, axis.y * s, axis.z * s, half.cos())
+    }
+
+    /// Rotation represented by the upper-left 3x3 block of `m`, which must be
+    /// a pure rotation in the row-vector convention of `to_matrix4`. Picks
+    /// the largest of `w`, `x`, `y` and `z` to divide by, so rotations close
+    /// to half a turn (negative trace) stay accurate.
+    pub fn from_matrix(m: &Matrix4<T>) -> Self
+    where
+        T: Copy
+            + Default
+            + One
+            + Add<Output = T>
+            + Sub<Output = T>
+            + Mul<Output = T>
+            + Div<Output = T>
+            + Sqrt
+            + PartialOrd,
+    {
+        let one = T::one();
+        let two = one + one;
+        let four = two + two;
+        let trace = m[0][0] + m[1][1] + m[2][2];
+
+        if trace > T::default() {
+            let s = (trace + one).sqrt() * two;
+            Self::new(
+                (m[1][2] - m[2][1]) / s,
+                (m[2][0] - m[0][2]) / s,
+                (m[0][1] - m[1][0]) / s,
+                s / four,
+            )
+        } else if m[0][0] > m[1][1] && m[0][0] > m[2][2] {
+            let s = (one + m[0][0] - m[1][1] - m[2][2]).sqrt() * two;
+            Self::new(
+                s / four,
+                (m[0][1] + m[1][0]) / s,
+                (m[2][0] + m[0][2]) / s,
+                (m[1][2] - m[2][1]) / s,
+            )
+        } else if m[1][1] > m[2][2] {
+            let s = (one + m[1][1] - m[0][0] - m[2][2]).sqrt() * two;
+            Self::new(
+                (m[0][1] + m[1][0]) / s,
+                s / four,
+                (m[1][2] + m[2][1]) / s,
+                (m[2][0] - m[0][2]) / s,
+            )
+        } else {
+            let s = (one + m[2][2] - m[0][0] - m[1][1]).sqrt() * two;
+            Self::new(
+                (m[2][0] + m[0][2]) / s,
+                (m[1][2] + m[2][1]) / s,
+                s / four,
+                (m[0][1] - m[1][0]) / s,
+            )
+        }
+    }
+
+    pub fn conjugate(&self) -> Self
+    where
+        T: Copy + Neg<Output = T>,
+    {
+        Self::new(-self.x, -self.y, -self.z, self.w)
+    }
+
+    pub fn norm(&self) -> T
+    where
+        T: Copy + Add<Output = T> + Mul<Output = T> + Sqrt,
+    {
+        let value = self.x * self.x + self.y * self.y + self.z * self.z + self.w * self.w;
+        value.sqrt()
+    }
+
+    pub fn normalize(&mut self)
+    where
+        T: Copy + Default + Add<Output = T> + Mul<Output = T> + Div<Output = T> + Sqrt + PartialOrd,
+    {
+        let len = self.norm();
+        if len > T::default() {
+            self.x = self.x / len;
+            self.y = self.y / len;
+            self.z = self.z / len;
+            self.w = self.w / len;
+        }
+    }
+
+    /// Spherical linear interpolation from `a` (t = 0) to `b` (t = 1) along
+    /// the shorter arc. Nearly identical rotations fall back to a normalized
+    /// linear interpolation to avoid dividing by a vanishing sine.
+    pub fn slerp(a: &Self, b: &Self, t: T) -> Self
+    where
+        T: Copy
+            + Default
+            + One
+            + Add<Output = T>
+            + Sub<Output = T>
+            + Mul<Output = T>
+            + Div<Output = T>
+            + Neg<Output = T>
+            + Sqrt
+            + Trig
+            + Acos
+            + FromF64
+            + PartialOrd,
+    {
+        let mut b = *b;
+        let mut dot = a.x * b.x + a.y * b.y + a.z * b.z + a.w * b.w;
+        if dot < T::default() {
+            b = Self::new(-b.x, -b.y, -b.z, -b.w);
+            dot = -dot;
+        }
+
+        let (wa, wb) = if dot > T::from_f64(0.9995) {
+            (T::one() - t, t)
+        } else {
+            let theta = dot.acos();
+            let sin_theta = theta.sin();
+            (
+                ((T::one() - t) * theta).sin() / sin_theta,
+                (t * theta).sin() / sin_theta,
+            )
+        };
+
+        let mut result = Self::new(
+            a.x * wa + b.x * wb,
+            a.y * wa + b.y * wb,
+            a.z * wa + b.z * wb,
+            a.w * wa + b.w * wb,
+        );
+        result.normalize();
+        result
+    }
+
+    /// Rotation matrix in the row-vector convention used by
+    /// `mul_vec_matrix`. The quaternion is expected to be normalized.
+    pub fn to_matrix4(&self) -> Matrix4<T>
+    where
+        T: Copy + Default + One + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+    {
+        let (x, y, z, w) = (self.x, self.y, self.z, self.w);
+        let one = T::one();
+        let two = one + one;
+
+        let mut result = Matrix4::identity();
+        result[0][0] = one - two * (y * y + z * z);
+        result[0][1] = two * (x * y + z * w);
+        result[0][2] = two * (x * z - y * w);
+        result[1][0] = two * (x * y - z * w);
+        result[1][1] = one - two * (x * x + z * z);
+        result[1][2] = two * (y * z + x * w);
+        result[2][0] = two * (x * z + y * w);
+        result[2][1] = two * (y * z - x * w);
+        result[2][2] = one - two * (x * x + y * y);
+        result
+    }
+}
+
+/// Hamilton product.
+impl<T> Mul for Quaternion<T>
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        let (a, b) = (self, rhs);
+        Self {
+            x: a.w * b.x + a.x * b.w + a.y * b.z - a.z * b.y,
+            y: a.w * b.y - a.x * b.z + a.y * b.w + a.z * b.x,
+            z: a.w * b.z + a.x * b.y - a.y * b.x + a.z * b.w,
+            w: a.w * b.w - a.x * b.x - a.y * b.y - a.z * b.z,
+        }
+    }
+}
+
+pub type Quatd = Quaternion<f64>;
+pub type Quatf = Quaternion<f32>;
+
+#[cfg(test)]
+mod tests {
+    use super::{Quatd, Quaternion};
+    use crate::{assert_approx_eq, ApproxEq, Mat4d, Tuple, Vec3};
+
+    #[test]
+    fn identity_and_conjugate() {
+        let identity: Quatd = Quaternion::identity();
+        assert!(identity == Quaternion::new(0.0, 0.0, 0.0, 1.0));
+
+        let q = Quaternion::new(1.0, -2.0, 3.0, 4.0);
+        assert!(q.conjugate() == Quaternion::new(-1.0, 2.0, -3.0, 4.0));
+        assert!(q * identity == q);
+        assert!(identity * q == q);
+    }
+
+    #[test]
+    fn hamilton_product() {
+        let i = Quaternion::new(1, 0, 0, 0);
+        let j = Quaternion::new(0, 1, 0, 0);
+        let k = Quaternion::new(0, 0, 1, 0);
+        assert!(i * j == k);
+        assert!(j * k == i);
+        assert!(k * i == j);
+        assert!(i * i == Quaternion::new(0, 0, 0, -1));
+    }
+
+    #[test]
+    fn norm_and_normalize() {
+        let mut q: Quatd = Quaternion::new(1.0, 2.0, 2.0, 4.0);
+        assert_approx_eq!(q.norm(), 5.0);
+        q.normalize();
+        assert_approx_eq!(q.norm(), 1.0);
+        assert_approx_eq!(q.w, 0.8);
+
+        let q = Quatd::from_axis_angle(&Vec3::new(0.3, -1.0, 2.0), 1.2);
+        assert_approx_eq!(q.norm(), 1.0);
+        let product = q * q.conjugate();
+        assert_approx_eq!(product.x, 0.0);
+        assert_approx_eq!(product.y, 0.0);
+        assert_approx_eq!(product.z, 0.0);
+        assert_approx_eq!(product.w, 1.0);
+    }
+
+    #[test]
+    fn to_matrix_matches_rotation_axis() {
+        let axis = Vec3::new(1.0, 2.0, -0.5);
+        let angle = 0.9;
+        let m = Quatd::from_axis_angle(&axis, angle).to_matrix4();
+        let expected = Mat4d::rotation_axis(&axis, angle);
+        for i in 0..4 {
+            for j in 0..4 {
+                assert_approx_eq!(m[i][j], expected[i][j]);
+            }
+        }
+    }
+
+    #[test]
+    fn matrix_round_trip() {
+        let cases = [
+            (Vec3::new(1.0, 2.0, -0.5), 0.9),
+            (Vec3::new(1.0, 0.1, 0.0), 3.1),
+            (Vec3::new(0.1, -1.0, 0.2), 3.0),
+            (Vec3::new(0.0, 0.2, 1.0), -3.1),
+            (Vec3::new(0.0, 0.0, 1.0), 0.0),
+        ];
+        for (axis, angle) in cases {
+            let m = Mat4d::rotation_axis(&axis, angle);
+            let q = Quatd::from_matrix(&m);
+            assert_approx_eq!(q.norm(), 1.0, 1e-6);
+            assert!(q.to_matrix4().approx_eq(&m, 1e-6));
+
+            let expected = Quatd::from_axis_angle(&axis, angle);
+            let dot = q.x * expected.x + q.y * expected.y + q.z * expected.z + q.w * expected.w;
+            assert_approx_eq!(dot.abs(), 1.0, 1e-6);
+        }
+    }
+
+    #[test]
+    fn slerp_endpoints_and_midpoint() {
+        let axis = Vec3::new(0.0, 0.0, 1.0);
+        let a = Quatd::identity();
+        let b = Quatd::from_axis_angle(&axis, 2.0);
+
+        let start = Quaternion::slerp(&a, &b, 0.0);
+        assert_approx_eq!(start.z, a.z);
+        assert_approx_eq!(start.w, a.w);
+
+        let end = Quaternion::slerp(&a, &b, 1.0);
+        assert_approx_eq!(end.z, b.z);
+        assert_approx_eq!(end.w, b.w);
+
+        let mid = Quaternion::slerp(&a, &b, 0.5);
+        let expected = Quatd::from_axis_angle(&axis, 1.0);
+        assert_approx_eq!(mid.x, expected.x);
+        assert_approx_eq!(mid.y, expected.y);
+        assert_approx_eq!(mid.z, expected.z);
+        assert_approx_eq!(mid.w, expected.w);
+    }
+
+    #[test]
+    fn slerp_short_path_and_nearly_equal() {
+        let axis = Vec3::new(1.0, 0.0, 0.0);
+        let a = Quatd::from_axis_angle(&axis, 0.2);
+        let b = Quatd::from_axis_angle(&axis, 0.6);
+        let flipped = Quaternion::new(-b.x, -b.y, -b.z, -b.w);
+
+        let mid = Quaternion::slerp(&a, &flipped, 0.5);
+        let expected = Quatd::from_axis_angle(&axis, 0.4);
+        assert_approx_eq!(mid.x, expected.x);
+        assert_approx_eq!(mid.w, expected.w);
+
+        let c = Quatd::from_axis_angle(&axis, 0.2001);
+        let mid = Quaternion::slerp(&a, &c, 0.5);
+        assert_approx_eq!(mid.norm(), 1.0);
+        assert_approx_eq!(mid.x, Quatd::from_axis_angle(&axis, 0.20005).x);
+    }
+}
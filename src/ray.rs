@@ -0,0 +1,144 @@
+use core::ops::{Add, Div, Mul, MulAssign, Sub};
+
+use crate::{One, Point3, Sqrt, Vec3, Zero};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ray<T> {
+    pub origin: Point3<T>,
+    pub direction: Vec3<T>,
+}
+
+impl<T> Ray<T> {
+    /// Creates a ray with a normalized copy of `direction`.
+    pub fn new(origin: Point3<T>, direction: Vec3<T>) -> Self
+    where
+        T: Copy
+            + Add<Output = T>
+            + Mul<Output = T>
+            + Div<Output = T>
+            + MulAssign
+            + Zero
+            + One
+            + Sqrt
+            + PartialOrd,
+    {
+        let mut direction = direction;
+        direction.normalize();
+        Self { origin, direction }
+    }
+
+    /// Point reached after travelling `t` along the direction.
+    pub fn at(&self, t: T) -> Point3<T>
+    where
+        T: Copy + Add<Output = T> + Mul<Output = T>,
+    {
+        self.origin + self.direction * t
+    }
+
+    /// Moller-Trumbore ray/triangle test. On a hit returns `(t, u, v)` with
+    /// `t >= 0` the ray parameter and the hit point equal to
+    /// `Point3::barycentric_uv(a, b, c, u, v)`, so `u` and `v` weight `a` and
+    /// `b`. Rays parallel to the triangle miss. With `cull_backfaces`, only
+    /// triangles whose vertices appear counter-clockwise from the ray origin
+    /// are hit, matching the facing of `Vec3::triangle_normal`.
+    pub fn intersect_triangle(
+        &self,
+        a: &Point3<T>,
+        b: &Point3<T>,
+        c: &Point3<T>,
+        cull_backfaces: bool,
+    ) -> Option<(T, T, T)>
+    where
+        T: Copy
+            + Default
+            + One
+            + Add<Output = T>
+            + Sub<Output = T>
+            + Mul<Output = T>
+            + Div<Output = T>
+            + PartialOrd,
+    {
+        let zero = T::default();
+        let one = T::one();
+        let e1 = b.difference(a);
+        let e2 = c.difference(a);
+
+        let p = self.direction.cross(&e2);
+        let det = e1.dot(&p);
+        if det == zero || (cull_backfaces && det < zero) {
+            return None;
+        }
+        let inv_det = one / det;
+
+        let s = self.origin.difference(a);
+        let u = s.dot(&p) * inv_det;
+        if u < zero || u > one {
+            return None;
+        }
+
+        let q = s.cross(&e1);
+        let v = self.direction.dot(&q) * inv_det;
+        if v < zero || u + v > one {
+            return None;
+        }
+
+        let t = e2.dot(&q) * inv_det;
+        if t < zero {
+            return None;
+        }
+        Some((t, one - u - v, u))
+    }
+}
+
+pub type Rayd = Ray<f64>;
+pub type Rayf = Ray<f32>;
+
+#[cfg(test)]
+mod tests {
+    use super::Rayd;
+    use crate::{assert_approx_eq, Point3, Tuple, Vec3};
+
+    #[test]
+    fn create_ray() {
+        let ray = Rayd::new(Point3::new(1.0, 2.0, 3.0), Vec3::new(0.0, 3.0, 4.0));
+        assert_eq!(ray.origin, Point3::new(1.0, 2.0, 3.0));
+        assert_approx_eq!(ray.direction.x, 0.0, 1e-12);
+        assert_approx_eq!(ray.direction.y, 0.6, 1e-12);
+        assert_approx_eq!(ray.direction.z, 0.8, 1e-12);
+    }
+
+    #[test]
+    fn point_along_ray() {
+        let ray = Rayd::new(Point3::new(1.0, 2.0, 3.0), Vec3::new(0.0, 0.0, -5.0));
+        assert_eq!(ray.at(0.0), ray.origin);
+        assert_eq!(ray.at(2.0), Point3::new(1.0, 2.0, 1.0));
+        assert_eq!(ray.at(-1.0), Point3::new(1.0, 2.0, 4.0));
+    }
+
+    #[test]
+    fn intersect_triangle() {
+        let a = Point3::new(0.0, 0.0, 0.0);
+        let b = Point3::new(1.0, 0.0, 0.0);
+        let c = Point3::new(0.0, 1.0, 0.0);
+
+        let down = Rayd::new(Point3::new(0.25, 0.25, 1.0), Vec3::new(0.0, 0.0, -1.0));
+        let (t, u, v) = down.intersect_triangle(&a, &b, &c, true).unwrap();
+        assert_eq!((t, u, v), (1.0, 0.5, 0.25));
+        assert_eq!(down.at(t), Point3::barycentric_uv(&a, &b, &c, u, v));
+
+        // Seen from below the vertices are clockwise.
+        let up = Rayd::new(Point3::new(0.25, 0.25, -1.0), Vec3::new(0.0, 0.0, 1.0));
+        assert_eq!(
+            up.intersect_triangle(&a, &b, &c, false),
+            Some((1.0, 0.5, 0.25))
+        );
+        assert_eq!(up.intersect_triangle(&a, &b, &c, true), None);
+
+        let outside = Rayd::new(Point3::new(1.0, 1.0, 1.0), Vec3::new(0.0, 0.0, -1.0));
+        assert_eq!(outside.intersect_triangle(&a, &b, &c, false), None);
+        let behind = Rayd::new(Point3::new(0.25, 0.25, -1.0), Vec3::new(0.0, 0.0, -1.0));
+        assert_eq!(behind.intersect_triangle(&a, &b, &c, false), None);
+        let parallel = Rayd::new(Point3::new(-1.0, 0.25, 0.0), Vec3::new(1.0, 0.0, 0.0));
+        assert_eq!(parallel.intersect_triangle(&a, &b, &c, false), None);
+    }
+}
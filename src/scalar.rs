@@ -0,0 +1,139 @@
+use core::ops::{Add, Div, Mul, Sub};
+
+use crate::{FromF64, One, Pi};
+
+/// Restricts `x` to `[lo, hi]`. A NaN `x` is returned unchanged.
+pub fn clamp<T: PartialOrd>(x: T, lo: T, hi: T) -> T {
+    if x < lo {
+        lo
+    } else if x > hi {
+        hi
+    } else {
+        x
+    }
+}
+
+/// Position of `x` between `edge0` and `edge1`, clamped to `[0, 1]`. Equal
+/// edges give a hard step: 0 below `edge0`, 1 from it on.
+fn unit_step<T>(edge0: T, edge1: T, x: T) -> T
+where
+    T: Copy + Default + One + Sub<Output = T> + Div<Output = T> + PartialOrd,
+{
+    if edge0 == edge1 {
+        return if x < edge0 { T::default() } else { T::one() };
+    }
+    clamp((x - edge0) / (edge1 - edge0), T::default(), T::one())
+}
+
+/// Hermite interpolation `3t^2 - 2t^3` of `t = (x - edge0) / (edge1 - edge0)`
+/// clamped to `[0, 1]`, as in GLSL. Has zero slope at both edges.
+pub fn smoothstep<T>(edge0: T, edge1: T, x: T) -> T
+where
+    T: Copy
+        + Default
+        + One
+        + Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + Div<Output = T>
+        + PartialOrd,
+{
+    let t = unit_step(edge0, edge1, x);
+    let two = T::one() + T::one();
+    t * t * (two + T::one() - two * t)
+}
+
+/// Perlin's `6t^5 - 15t^4 + 10t^3`, which like `smoothstep` maps the edges
+/// to 0 and 1 but also has zero second derivative there.
+pub fn smootherstep<T>(edge0: T, edge1: T, x: T) -> T
+where
+    T: Copy
+        + Default
+        + One
+        + Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + Div<Output = T>
+        + PartialOrd,
+{
+    let t = unit_step(edge0, edge1, x);
+    let three = T::one() + T::one() + T::one();
+    let five = three + T::one() + T::one();
+    let (six, ten, fifteen) = (three + three, five + five, five * three);
+    t * t * t * (t * (t * six - fifteen) + ten)
+}
+
+/// Converts an angle in degrees to the radians the rotation constructors
+/// take.
+pub fn to_radians<T>(degrees: T) -> T
+where
+    T: Copy + Mul<Output = T> + Div<Output = T> + Pi + FromF64,
+{
+    degrees * (T::pi() / T::from_f64(180.0))
+}
+
+/// Converts an angle in radians to degrees.
+pub fn to_degrees<T>(radians: T) -> T
+where
+    T: Copy + Mul<Output = T> + Div<Output = T> + Pi + FromF64,
+{
+    radians * (T::from_f64(180.0) / T::pi())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{clamp, smootherstep, smoothstep, to_degrees, to_radians};
+    use crate::assert_approx_eq;
+    use std::f64::consts::{FRAC_PI_2, PI};
+
+    #[test]
+    fn clamp_to_range() {
+        assert_eq!(clamp(-1.5, 0.0, 1.0), 0.0);
+        assert_eq!(clamp(0.25, 0.0, 1.0), 0.25);
+        assert_eq!(clamp(3, -2, 2), 2);
+        assert!(clamp(f64::NAN, 0.0, 1.0).is_nan());
+    }
+
+    #[test]
+    fn smoothstep_edges_and_midpoint() {
+        assert_eq!(smoothstep(2.0, 4.0, 1.0), 0.0);
+        assert_eq!(smoothstep(2.0, 4.0, 2.0), 0.0);
+        assert_eq!(smoothstep(2.0, 4.0, 3.0), 0.5);
+        assert_eq!(smoothstep(2.0, 4.0, 4.0), 1.0);
+        assert_eq!(smoothstep(2.0, 4.0, 5.0), 1.0);
+        assert_eq!(smoothstep(0.0, 1.0, 0.25), 0.15625);
+        assert_eq!(smoothstep(4.0f32, 2.0, 3.5), 0.15625);
+    }
+
+    #[test]
+    fn smootherstep_edges_and_midpoint() {
+        assert_eq!(smootherstep(2.0, 4.0, 1.0), 0.0);
+        assert_eq!(smootherstep(2.0, 4.0, 2.0), 0.0);
+        assert_eq!(smootherstep(2.0, 4.0, 3.0), 0.5);
+        assert_eq!(smootherstep(2.0, 4.0, 4.0), 1.0);
+        assert_eq!(smootherstep(2.0, 4.0, 5.0), 1.0);
+        assert_eq!(smootherstep(0.0, 1.0, 0.25), 0.103515625);
+    }
+
+    #[test]
+    fn equal_edges_give_a_hard_step() {
+        assert_eq!(smoothstep(2.0, 2.0, 1.5), 0.0);
+        assert_eq!(smoothstep(2.0, 2.0, 2.0), 1.0);
+        assert_eq!(smoothstep(2.0, 2.0, 2.5), 1.0);
+        assert_eq!(smootherstep(2.0, 2.0, 1.5), 0.0);
+        assert_eq!(smootherstep(2.0, 2.0, 2.5), 1.0);
+        assert_eq!(smoothstep(1, 1, 0), 0);
+        assert_eq!(smoothstep(1, 1, 1), 1);
+    }
+
+    #[test]
+    fn degrees_and_radians() {
+        assert_approx_eq!(to_radians(180.0), PI, 1e-12);
+        assert_approx_eq!(to_radians(-90.0), -FRAC_PI_2, 1e-12);
+        assert_approx_eq!(to_degrees(PI), 180.0, 1e-12);
+        assert_approx_eq!(to_degrees(to_radians(37.5f64)), 37.5, 1e-12);
+
+        assert_approx_eq!(to_radians(180.0f32), core::f32::consts::PI, 1e-6);
+        assert_approx_eq!(to_degrees(core::f32::consts::FRAC_PI_2), 90.0f32, 1e-4);
+    }
+}
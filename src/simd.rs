@@ -0,0 +1,81 @@
+use crate::Matrix4;
+
+/// Same product as `multiply` for `Matrix4<f32>`, computing each result row
+/// with four-wide SSE operations. Row `i` of the result is
+/// `sum_k lhs[i][k] * rhs[k]`, summed in the same order as the scalar path,
+/// so the results are bit-identical. Falls back to `multiply` on targets
+/// without SSE.
+#[inline]
+pub fn multiply_simd(lhs: &Matrix4<f32>, rhs: &Matrix4<f32>) -> Matrix4<f32> {
+    #[cfg(target_arch = "x86_64")]
+    {
+        // SSE is part of the x86_64 baseline, so no runtime detection is needed.
+        use core::arch::x86_64::{
+            _mm_add_ps, _mm_loadu_ps, _mm_mul_ps, _mm_set1_ps, _mm_storeu_ps,
+        };
+
+        let mut result = Matrix4::zeros();
+        // SAFETY: SSE is always available on x86_64, and every load and store
+        // goes through a `[f32; 4]` row, which holds exactly four floats.
+        unsafe {
+            let b = [
+                _mm_loadu_ps(rhs[0].as_ptr()),
+                _mm_loadu_ps(rhs[1].as_ptr()),
+                _mm_loadu_ps(rhs[2].as_ptr()),
+                _mm_loadu_ps(rhs[3].as_ptr()),
+            ];
+            for i in 0..4 {
+                let a = lhs[i];
+                let mut row = _mm_mul_ps(_mm_set1_ps(a[0]), b[0]);
+                row = _mm_add_ps(row, _mm_mul_ps(_mm_set1_ps(a[1]), b[1]));
+                row = _mm_add_ps(row, _mm_mul_ps(_mm_set1_ps(a[2]), b[2]));
+                row = _mm_add_ps(row, _mm_mul_ps(_mm_set1_ps(a[3]), b[3]));
+                _mm_storeu_ps(result[i].as_mut_ptr(), row);
+            }
+        }
+        result
+    }
+
+    #[cfg(not(target_arch = "x86_64"))]
+    {
+        crate::multiply(lhs, rhs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::multiply_simd;
+    use crate::{multiply, Mat4f, Tuple, Vec3};
+
+    /// Small deterministic generator so the test needs no extra dependency.
+    fn random_matrix(state: &mut u32) -> Mat4f {
+        let mut m = Mat4f::zeros();
+        for row in m.m.iter_mut() {
+            for value in row.iter_mut() {
+                *state = state.wrapping_mul(1664525).wrapping_add(1013904223);
+                *value = (*state >> 8) as f32 / (1 << 24) as f32 * 200.0 - 100.0;
+            }
+        }
+        m
+    }
+
+    #[test]
+    fn matches_scalar_multiply() {
+        let mut state = 12345;
+        for _ in 0..100 {
+            let a = random_matrix(&mut state);
+            let b = random_matrix(&mut state);
+            let simd = multiply_simd(&a, &b);
+            let scalar = multiply(&a, &b);
+            for i in 0..4 {
+                for j in 0..4 {
+                    assert_eq!(simd[i][j].to_bits(), scalar[i][j].to_bits());
+                }
+            }
+        }
+
+        let rotation = Mat4f::rotation_axis(&Vec3::new(1.0, 2.0, 3.0), 0.5);
+        let identity = Mat4f::identity();
+        assert_eq!(multiply_simd(&rotation, &identity), rotation);
+    }
+}
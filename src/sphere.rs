@@ -0,0 +1,104 @@
+use core::ops::{Add, Div, Mul, Neg, Sub};
+
+use crate::{Point, Point3, Ray, Sqrt};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sphere<T> {
+    pub center: Point3<T>,
+    pub radius: T,
+}
+
+impl<T> Sphere<T> {
+    pub fn new(center: Point3<T>, radius: T) -> Self {
+        Self { center, radius }
+    }
+
+    /// Whether `p` lies inside or on the surface.
+    pub fn contains(&self, p: &Point3<T>) -> bool
+    where
+        T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + PartialOrd,
+    {
+        self.center.distance_squared(p) <= self.radius * self.radius
+    }
+
+    /// Solves `|origin + t * direction - center| = radius` for `t`. Like
+    /// `Aabb::intersect_ray`, returns the range of `t >= 0` spent inside the
+    /// sphere, so a ray starting inside yields `(0, t_exit)`. Returns `None`
+    /// when the ray misses or the sphere lies entirely behind the origin.
+    pub fn intersect_ray(&self, ray: &Ray<T>) -> Option<(T, T)>
+    where
+        T: Copy
+            + Default
+            + Add<Output = T>
+            + Sub<Output = T>
+            + Mul<Output = T>
+            + Div<Output = T>
+            + Neg<Output = T>
+            + Sqrt
+            + PartialOrd,
+    {
+        let zero = T::default();
+        let oc = ray.origin.difference(&self.center);
+
+        // Quadratic `a t^2 + 2 h t + c = 0`, written with the half
+        // coefficient `h` to drop the factors of two.
+        let a = ray.direction.dot(&ray.direction);
+        let h = ray.direction.dot(&oc);
+        let c = oc.dot(&oc) - self.radius * self.radius;
+        let discriminant = h * h - a * c;
+        if discriminant < zero {
+            return None;
+        }
+
+        let root = discriminant.sqrt();
+        let t_exit = (-h + root) / a;
+        if t_exit < zero {
+            return None;
+        }
+        let t_enter = (-h - root) / a;
+        Some((if t_enter < zero { zero } else { t_enter }, t_exit))
+    }
+}
+
+pub type Sphered = Sphere<f64>;
+pub type Spheref = Sphere<f32>;
+
+#[cfg(test)]
+mod tests {
+    use super::Sphered;
+    use crate::{Point3, Rayd, Tuple, Vec3};
+
+    #[test]
+    fn contains_point() {
+        let sphere = Sphered::new(Point3::new(1.0, 2.0, 3.0), 2.0);
+        assert!(sphere.contains(&Point3::new(1.0, 2.0, 3.0)));
+        assert!(sphere.contains(&Point3::new(1.0, 2.0, 5.0)));
+        assert!(!sphere.contains(&Point3::new(2.5, 3.5, 3.0)));
+    }
+
+    #[test]
+    fn ray_hits_from_outside() {
+        let sphere = Sphered::new(Point3::new(0.0, 0.0, -5.0), 2.0);
+        let ray = Rayd::new(Point3::new(0.0, 0.0, 0.0), Vec3::new(0.0, 0.0, -1.0));
+        assert_eq!(sphere.intersect_ray(&ray), Some((3.0, 7.0)));
+
+        let grazing = Rayd::new(Point3::new(2.0, 0.0, 0.0), Vec3::new(0.0, 0.0, -1.0));
+        assert_eq!(sphere.intersect_ray(&grazing), Some((5.0, 5.0)));
+    }
+
+    #[test]
+    fn ray_starts_inside() {
+        let sphere = Sphered::new(Point3::new(0.0, 0.0, -5.0), 2.0);
+        let ray = Rayd::new(Point3::new(0.0, 0.0, -4.0), Vec3::new(0.0, 0.0, -1.0));
+        assert_eq!(sphere.intersect_ray(&ray), Some((0.0, 3.0)));
+    }
+
+    #[test]
+    fn ray_misses() {
+        let sphere = Sphered::new(Point3::new(0.0, 0.0, -5.0), 2.0);
+        let beside = Rayd::new(Point3::new(3.0, 0.0, 0.0), Vec3::new(0.0, 0.0, -1.0));
+        assert_eq!(sphere.intersect_ray(&beside), None);
+        let away = Rayd::new(Point3::new(0.0, 0.0, 0.0), Vec3::new(0.0, 0.0, 1.0));
+        assert_eq!(sphere.intersect_ray(&away), None);
+    }
+}
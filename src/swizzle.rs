@@ -0,0 +1,98 @@
+use crate::{Vec2, Vec3};
+
+macro_rules! swizzle2 {
+    ($($name:ident: $a:ident, $b:ident;)*) => {
+        $(
+            pub fn $name(&self) -> Vec2<T> {
+                Vec2 {
+                    x: self.$a,
+                    y: self.$b,
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! swizzle3 {
+    ($($name:ident: $a:ident, $b:ident, $c:ident;)*) => {
+        $(
+            pub fn $name(&self) -> Vec3<T> {
+                Vec3 {
+                    x: self.$a,
+                    y: self.$b,
+                    z: self.$c,
+                }
+            }
+        )*
+    };
+}
+
+/// Shader-style swizzles, e.g. `v.xy()` or `v.zyx()`, covering every two-
+/// and three-component combination.
+impl<T: Copy> Vec3<T> {
+    swizzle2! {
+        xx: x, x;
+        xy: x, y;
+        xz: x, z;
+        yx: y, x;
+        yy: y, y;
+        yz: y, z;
+        zx: z, x;
+        zy: z, y;
+        zz: z, z;
+    }
+
+    swizzle3! {
+        xxx: x, x, x;
+        xxy: x, x, y;
+        xxz: x, x, z;
+        xyx: x, y, x;
+        xyy: x, y, y;
+        xyz: x, y, z;
+        xzx: x, z, x;
+        xzy: x, z, y;
+        xzz: x, z, z;
+        yxx: y, x, x;
+        yxy: y, x, y;
+        yxz: y, x, z;
+        yyx: y, y, x;
+        yyy: y, y, y;
+        yyz: y, y, z;
+        yzx: y, z, x;
+        yzy: y, z, y;
+        yzz: y, z, z;
+        zxx: z, x, x;
+        zxy: z, x, y;
+        zxz: z, x, z;
+        zyx: z, y, x;
+        zyy: z, y, y;
+        zyz: z, y, z;
+        zzx: z, z, x;
+        zzy: z, z, y;
+        zzz: z, z, z;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Tuple, Tuple2, Vec2, Vec3};
+
+    #[test]
+    fn two_component_swizzles() {
+        let v = Vec3::new(1, 2, 3);
+        assert!(v.xy() == Vec2::new(1, 2));
+        assert!(v.xz() == Vec2::new(1, 3));
+        assert!(v.zy() == Vec2::new(3, 2));
+        assert!(v.yy() == Vec2::new(2, 2));
+    }
+
+    #[test]
+    fn three_component_swizzles() {
+        let v = Vec3::new(1.0, 2.0, 3.0);
+        assert_eq!(v.zyx(), Vec3::new(3.0, 2.0, 1.0));
+        assert_eq!(v.xyz(), v);
+        assert_eq!(v.xxx(), Vec3::new(1.0, 1.0, 1.0));
+        assert_eq!(v.yzx(), Vec3::new(2.0, 3.0, 1.0));
+        assert_eq!(v.zyx().zyx(), v);
+    }
+}
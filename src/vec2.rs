@@ -0,0 +1,264 @@
+use core::ops::{Add, Div, Index, IndexMut, Mul, MulAssign, Neg, Sub};
+
+use crate::{One, Sqrt, Vector, Zero};
+
+pub trait Tuple2<T> {
+    fn new(x: T, y: T) -> Self
+    where
+        T: Copy;
+
+    fn zeros() -> Self
+    where
+        T: Default;
+}
+
+/// The 2D-only part of the vector interface; the rest lives in `Vector`.
+pub trait Vector2<T>: Tuple2<T> + Vector<T> {
+    /// Z component of the cross product of the two vectors lifted into 3D.
+    fn cross(&self, other: &Self) -> T
+    where
+        T: Copy + Mul<Output = T> + Sub<Output = T>;
+}
+
+#[derive(Default, Clone, Copy, PartialEq)]
+pub struct Vec2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Tuple2<T> for Vec2<T> {
+    fn new(x: T, y: T) -> Self
+    where
+        T: Copy,
+    {
+        Self { x, y }
+    }
+
+    fn zeros() -> Self
+    where
+        T: Default,
+    {
+        Self {
+            ..Default::default()
+        }
+    }
+}
+
+impl<T> Vec2<T> {
+    /// Method form of `Vector::dot`, so `a.dot(&b)` and `Vec2::dot(&a, &b)`
+    /// both compile.
+    pub fn dot(&self, other: &Self) -> T
+    where
+        T: Copy + Add<Output = T> + Mul<Output = T>,
+    {
+        <Self as Vector<T>>::dot(self, other)
+    }
+}
+
+impl<T> Vector<T> for Vec2<T> {
+    fn dot(lhs: &Self, rhs: &Self) -> T
+    where
+        T: Copy + Add<Output = T> + Mul<Output = T>,
+    {
+        lhs.x * rhs.x + lhs.y * rhs.y
+    }
+
+    fn normalize(&mut self)
+    where
+        T: Copy
+            + Add<Output = T>
+            + Mul<Output = T>
+            + Div<Output = T>
+            + MulAssign
+            + Zero
+            + One
+            + Sqrt
+            + PartialOrd,
+    {
+        let len = self.magnitude();
+        if len > T::zero() {
+            let inv_len = T::one() / len;
+            self.x *= inv_len;
+            self.y *= inv_len;
+        }
+    }
+}
+
+impl<T> Vector2<T> for Vec2<T> {
+    fn cross(&self, other: &Self) -> T
+    where
+        T: Copy + Mul<Output = T> + Sub<Output = T>,
+    {
+        self.x * other.y - self.y * other.x
+    }
+}
+
+impl<T: Add<Output = T>> Add for Vec2<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self {
+            x: self.x + rhs.x,
+            y: self.y + rhs.y,
+        }
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Vec2<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y,
+        }
+    }
+}
+
+impl<T: Copy + Mul<Output = T>> Mul<T> for Vec2<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        Self {
+            x: self.x * rhs,
+            y: self.y * rhs,
+        }
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Vec2<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            x: -self.x,
+            y: -self.y,
+        }
+    }
+}
+
+impl<T> Index<usize> for Vec2<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        match index {
+            0 => &self.x,
+            1 => &self.y,
+            _ => panic!("Out of bound access in Vec2<T>!"),
+        }
+    }
+}
+
+impl<T> IndexMut<usize> for Vec2<T> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        match index {
+            0 => &mut self.x,
+            1 => &mut self.y,
+            _ => panic!("Out of bound access in Vec2<T>!"),
+        }
+    }
+}
+
+pub type Vec2d = Vec2<f64>;
+pub type Vec2f = Vec2<f32>;
+
+#[cfg(test)]
+mod tests {
+    use super::{Tuple2, Vec2, Vector2};
+    use crate::assert_approx_eq;
+    use crate::Vector;
+
+    #[test]
+    fn create_and_modify_vector() {
+        let zeros: Vec2<f64> = Vec2::zeros();
+        assert_eq!(zeros.x, 0.0);
+        assert_eq!(zeros[0], 0.0);
+        assert_eq!(zeros.y, 0.0);
+        assert_eq!(zeros[1], 0.0);
+
+        let vec: Vec2<f64> = Vec2::new(1.1, 2.2);
+        assert_eq!(vec.x, 1.1);
+        assert_eq!(vec.y, 2.2);
+
+        let mut vec: Vec2<f64> = Vec2::zeros();
+        vec[0] = 1.1;
+        vec[1] = 2.2;
+        assert_eq!(vec[0], 1.1);
+        assert_eq!(vec[1], 2.2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn out_of_bound_access() {
+        let vec: Vec2<f64> = Vec2::zeros();
+        let _ = vec[2];
+    }
+
+    #[test]
+    fn dot_product() {
+        let lhs: Vec2<f64> = Vec2::new(3.1, 5.0);
+        let rhs: Vec2<f64> = Vec2::new(11.27, -9.0);
+        let dot = Vec2::dot(&lhs, &rhs);
+
+        assert_approx_eq!(dot, -10.063, 1e-12);
+        assert_approx_eq!(lhs.dot(&rhs), -10.063, 1e-12);
+    }
+
+    #[test]
+    fn cross_product() {
+        let lhs: Vec2<f64> = Vec2::new(1.0, 0.0);
+        let rhs: Vec2<f64> = Vec2::new(0.0, 1.0);
+        assert_eq!(lhs.cross(&rhs), 1.0);
+        assert_eq!(rhs.cross(&lhs), -1.0);
+
+        let lhs: Vec2<i32> = Vec2::new(3, 5);
+        let rhs: Vec2<i32> = Vec2::new(2, -4);
+        assert_eq!(lhs.cross(&rhs), -22);
+    }
+
+    #[test]
+    fn vec_magnitude() {
+        let vec: Vec2<f64> = Vec2::new(3.0, 4.0);
+        assert_eq!(vec.magnitude(), 5.0);
+    }
+
+    #[test]
+    fn basic_vector_ops() {
+        let lhs: Vec2<f64> = Vec2::new(1.0, 2.0);
+        let rhs: Vec2<f64> = Vec2::new(4.0, 5.0);
+        let sum = lhs + rhs;
+        let diff = lhs - rhs;
+        let scaled = lhs * 3.0;
+        let negated = -lhs;
+
+        assert_eq!(sum.x, 5.0);
+        assert_eq!(sum.y, 7.0);
+
+        assert_eq!(diff.x, -3.0);
+        assert_eq!(diff.y, -3.0);
+
+        assert_eq!(scaled.x, 3.0);
+        assert_eq!(scaled.y, 6.0);
+
+        assert_eq!(negated.x, -1.0);
+        assert_eq!(negated.y, -2.0);
+    }
+
+    #[test]
+    fn normalize_vector() {
+        let mut vec: Vec2<f64> = Vec2::new(3.0, 4.0);
+        vec.normalize();
+        assert_approx_eq!(vec.x, 0.6, 1e-12);
+        assert_approx_eq!(vec.y, 0.8, 1e-12);
+
+        let mut vec: Vec2<f32> = Vec2::new(3.0, 4.0);
+        vec.normalize();
+        assert_eq!(vec.x, 0.6);
+        assert_eq!(vec.y, 0.8);
+
+        let mut zeros: Vec2<f64> = Vec2::zeros();
+        zeros.normalize();
+        assert_eq!(zeros.x, 0.0);
+        assert_eq!(zeros.y, 0.0);
+    }
+}
@@ -0,0 +1,283 @@
+use core::ops::{Add, Div, Index, IndexMut, Mul, MulAssign, Neg, Sub};
+
+use crate::{Matrix4, One, Point3, Sqrt, Tuple, Vec3, Vector, Zero};
+
+/// Homogeneous coordinates. Points carry `w = 1`, directions `w = 0`.
+#[derive(Default, Clone, Copy, PartialEq)]
+pub struct Vec4<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+    pub w: T,
+}
+
+impl<T> Vec4<T> {
+    pub fn new(x: T, y: T, z: T, w: T) -> Self
+    where
+        T: Copy,
+    {
+        Self { x, y, z, w }
+    }
+
+    pub fn zeros() -> Self
+    where
+        T: Default,
+    {
+        Self {
+            ..Default::default()
+        }
+    }
+
+    pub fn from_point(p: &Point3<T>) -> Self
+    where
+        T: Copy + One,
+    {
+        Self::new(p.x, p.y, p.z, T::one())
+    }
+
+    pub fn from_vec(v: &Vec3<T>) -> Self
+    where
+        T: Copy + Default,
+    {
+        Self::new(v.x, v.y, v.z, T::default())
+    }
+
+    /// Performs the perspective divide by `w`.
+    pub fn to_point(&self) -> Point3<T>
+    where
+        T: Copy + Div<Output = T>,
+    {
+        Point3::new(self.x / self.w, self.y / self.w, self.z / self.w)
+    }
+
+    /// Cartesian point for these homogeneous coordinates, i.e. the divide by
+    /// `w` that `mul_point_matrix` performs implicitly. Same as `to_point`.
+    pub fn to_cartesian(&self) -> Point3<T>
+    where
+        T: Copy + Div<Output = T>,
+    {
+        self.to_point()
+    }
+
+    /// Method form of `Vector::dot`, so `a.dot(&b)` and `Vec4::dot(&a, &b)`
+    /// both compile.
+    pub fn dot(&self, other: &Self) -> T
+    where
+        T: Copy + Add<Output = T> + Mul<Output = T>,
+    {
+        <Self as Vector<T>>::dot(self, other)
+    }
+}
+
+/// Treats all four components alike, so `normalize` also scales `w`.
+impl<T> Vector<T> for Vec4<T> {
+    fn dot(lhs: &Self, rhs: &Self) -> T
+    where
+        T: Copy + Add<Output = T> + Mul<Output = T>,
+    {
+        lhs.x * rhs.x + lhs.y * rhs.y + lhs.z * rhs.z + lhs.w * rhs.w
+    }
+
+    fn normalize(&mut self)
+    where
+        T: Copy
+            + Add<Output = T>
+            + Mul<Output = T>
+            + Div<Output = T>
+            + MulAssign
+            + Zero
+            + One
+            + Sqrt
+            + PartialOrd,
+    {
+        let len = self.magnitude();
+        if len > T::zero() {
+            let inv_len = T::one() / len;
+            self.x *= inv_len;
+            self.y *= inv_len;
+            self.z *= inv_len;
+            self.w *= inv_len;
+        }
+    }
+}
+
+impl<T> Point3<T> {
+    /// Homogeneous coordinates with `w = 1`.
+    pub fn to_homogeneous(&self) -> Vec4<T>
+    where
+        T: Copy + One,
+    {
+        Vec4::from_point(self)
+    }
+}
+
+impl<T> Vec3<T> {
+    /// Homogeneous coordinates with `w = 0`, so translations do not apply.
+    pub fn to_homogeneous(&self) -> Vec4<T>
+    where
+        T: Copy + Default,
+    {
+        Vec4::from_vec(self)
+    }
+}
+
+impl<T: Add<Output = T>> Add for Vec4<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self {
+            x: self.x + rhs.x,
+            y: self.y + rhs.y,
+            z: self.z + rhs.z,
+            w: self.w + rhs.w,
+        }
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Vec4<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y,
+            z: self.z - rhs.z,
+            w: self.w - rhs.w,
+        }
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Vec4<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            x: -self.x,
+            y: -self.y,
+            z: -self.z,
+            w: -self.w,
+        }
+    }
+}
+
+/// Transforms the vector as a row vector, matching `mul_point_matrix`.
+impl<T: Copy + Add<Output = T> + Mul<Output = T>> Mul<Matrix4<T>> for Vec4<T> {
+    type Output = Self;
+
+    fn mul(self, m: Matrix4<T>) -> Self::Output {
+        let v = self;
+        Self {
+            x: v[0] * m[0][0] + v[1] * m[1][0] + v[2] * m[2][0] + v[3] * m[3][0],
+            y: v[0] * m[0][1] + v[1] * m[1][1] + v[2] * m[2][1] + v[3] * m[3][1],
+            z: v[0] * m[0][2] + v[1] * m[1][2] + v[2] * m[2][2] + v[3] * m[3][2],
+            w: v[0] * m[0][3] + v[1] * m[1][3] + v[2] * m[2][3] + v[3] * m[3][3],
+        }
+    }
+}
+
+impl<T> Index<usize> for Vec4<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        match index {
+            0 => &self.x,
+            1 => &self.y,
+            2 => &self.z,
+            3 => &self.w,
+            _ => panic!("Out of bound access in Vec4<T>!"),
+        }
+    }
+}
+
+impl<T> IndexMut<usize> for Vec4<T> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        match index {
+            0 => &mut self.x,
+            1 => &mut self.y,
+            2 => &mut self.z,
+            3 => &mut self.w,
+            _ => panic!("Out of bound access in Vec4<T>!"),
+        }
+    }
+}
+
+pub type Vec4d = Vec4<f64>;
+pub type Vec4f = Vec4<f32>;
+
+#[cfg(test)]
+mod tests {
+    use super::Vec4;
+    use crate::{mul_point_matrix, Mat4d, Point3, Tuple, Vec3};
+
+    #[test]
+    fn create_and_modify_vector() {
+        let zeros: Vec4<f64> = Vec4::zeros();
+        for i in 0..4 {
+            assert_eq!(zeros[i], 0.0);
+        }
+
+        let mut vec: Vec4<f64> = Vec4::new(1.1, 2.2, 3.3, 4.4);
+        assert_eq!(vec.x, 1.1);
+        assert_eq!(vec.y, 2.2);
+        assert_eq!(vec.z, 3.3);
+        assert_eq!(vec.w, 4.4);
+
+        vec[3] = 5.5;
+        assert_eq!(vec[3], 5.5);
+    }
+
+    #[test]
+    fn basic_vector_ops() {
+        let lhs: Vec4<f64> = Vec4::new(1.0, 2.0, 3.0, 4.0);
+        let rhs: Vec4<f64> = Vec4::new(4.0, 3.0, 2.0, 1.0);
+
+        assert!(lhs + rhs == Vec4::new(5.0, 5.0, 5.0, 5.0));
+        assert!(lhs - rhs == Vec4::new(-3.0, -1.0, 1.0, 3.0));
+        assert!(-lhs == Vec4::new(-1.0, -2.0, -3.0, -4.0));
+        assert_eq!(Vec4::dot(&lhs, &rhs), 20.0);
+        assert_eq!(lhs.dot(&rhs), 20.0);
+    }
+
+    #[test]
+    fn point_and_vector_conversions() {
+        let point = Vec4::from_point(&Point3::new(1.0, 2.0, 3.0));
+        assert!(point == Vec4::new(1.0, 2.0, 3.0, 1.0));
+
+        let vec = Vec4::from_vec(&Vec3::new(1.0, 2.0, 3.0));
+        assert!(vec == Vec4::new(1.0, 2.0, 3.0, 0.0));
+
+        let point = Vec4::new(2.0, 4.0, 6.0, 2.0).to_point();
+        assert!(point == Point3::new(1.0, 2.0, 3.0));
+    }
+
+    #[test]
+    fn homogeneous_round_trip() {
+        let p = Point3::new(1.5, -2.0, 3.25);
+        let h = p.to_homogeneous();
+        assert!(h == Vec4::new(1.5, -2.0, 3.25, 1.0));
+        assert!(h.to_cartesian() == p);
+        assert!(Vec4::new(6.0, -8.0, 13.0, 4.0).to_cartesian() == p);
+
+        let v = Vec3::new(1.5, -2.0, 3.25);
+        assert!(v.to_homogeneous() == Vec4::new(1.5, -2.0, 3.25, 0.0));
+
+        let m = Mat4d::translation(1.0, 2.0, 3.0);
+        assert!((p.to_homogeneous() * m).to_cartesian() == mul_point_matrix(&p, &m));
+        assert!(v.to_homogeneous() * m == v.to_homogeneous());
+    }
+
+    #[test]
+    fn transform_by_matrix() {
+        let m = Mat4d::new(
+            2.0, 0.0, 0.0, 0.0, 0.0, 3.0, 0.0, 0.0, 0.0, 0.0, 4.0, 1.0, 1.0, 2.0, 3.0, 1.0,
+        );
+        let p = Point3::new(1.0, 1.0, 1.0);
+
+        let transformed = Vec4::from_point(&p) * m;
+        assert!(transformed == Vec4::new(3.0, 5.0, 7.0, 2.0));
+        assert!(transformed.to_point() == mul_point_matrix(&p, &m));
+
+        let direction = Vec4::from_vec(&Vec3::new(1.0, 1.0, 0.0)) * m;
+        assert!(direction == Vec4::new(2.0, 3.0, 0.0, 0.0));
+    }
+}